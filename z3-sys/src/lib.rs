@@ -16,3 +16,4 @@ pub use Z3_lbool::*;
 pub use Z3_param_kind::*;
 pub use Z3_parameter_kind::*;
 pub use Z3_sort_kind::*;
+pub use Z3_symbol_kind::*;
@@ -0,0 +1,54 @@
+use std::ffi::CStr;
+use std::fmt;
+use z3_sys::*;
+use {ApplyResult, Context, Goal};
+
+impl<'ctx> ApplyResult<'ctx> {
+    pub(crate) unsafe fn from_raw(
+        ctx: &'ctx Context,
+        z3_apply_result: Z3_apply_result,
+    ) -> ApplyResult<'ctx> {
+        Z3_apply_result_inc_ref(ctx.z3_ctx, z3_apply_result);
+        ApplyResult {
+            ctx,
+            z3_apply_result,
+        }
+    }
+
+    /// Return the number of subgoals produced by the tactic.
+    pub fn num_subgoals(&self) -> u32 {
+        unsafe { Z3_apply_result_get_num_subgoals(self.ctx.z3_ctx, self.z3_apply_result) }
+    }
+
+    /// Return the subgoals produced by the tactic. The original goal is
+    /// satisfiable if and only if one of the subgoals is.
+    pub fn subgoals(&self) -> Vec<Goal<'ctx>> {
+        (0..self.num_subgoals())
+            .map(|i| unsafe {
+                Goal::from_raw(
+                    self.ctx,
+                    Z3_apply_result_get_subgoal(self.ctx.z3_ctx, self.z3_apply_result, i),
+                )
+            })
+            .collect()
+    }
+}
+
+impl<'ctx> fmt::Display for ApplyResult<'ctx> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        let p = unsafe { Z3_apply_result_to_string(self.ctx.z3_ctx, self.z3_apply_result) };
+        if p.is_null() {
+            return Result::Err(fmt::Error);
+        }
+        match unsafe { CStr::from_ptr(p) }.to_str() {
+            Ok(s) => write!(f, "{}", s),
+            Err(_) => Result::Err(fmt::Error),
+        }
+    }
+}
+
+impl<'ctx> Drop for ApplyResult<'ctx> {
+    fn drop(&mut self) {
+        unsafe { Z3_apply_result_dec_ref(self.ctx.z3_ctx, self.z3_apply_result) };
+    }
+}
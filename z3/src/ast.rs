@@ -1,59 +1,108 @@
+//! Statically typed wrappers around [`Ast`](../struct.Ast.html).
+//!
+//! Each wrapper only exposes the operations that are valid for its sort,
+//! so e.g. `bvadd` on an integer is rejected at compile time rather than
+//! by Z3.
+//!
+//! # Examples
+//! ```
+//! # use z3::{Config, Context, SatResult, Solver};
+//! use z3::ast::{Int, TypedAst, BV};
+//! # let cfg = Config::new();
+//! # let ctx = Context::new(&cfg);
+//! # let solver = Solver::new(&ctx);
+//! let x = Int::new_const(&ctx, "x");
+//! let y = x.add(&[&Int::from_i64(&ctx, 1)]);
+//! solver.assert(y._eq(&Int::from_i64(&ctx, 3)).as_ast());
+//!
+//! // Checked conversion from an untyped `Ast`.
+//! let b = ctx.named_bitvector_const("b", 8);
+//! assert!(Int::from_ast(&b).is_none());
+//! assert_eq!(BV::from_ast(&b).unwrap().get_size(), 8);
+//!
+//! assert_eq!(solver.check(), SatResult::Sat);
+//! let model = solver.get_model();
+//! assert_eq!(2, model.eval(x.as_ast()).unwrap().as_i64().unwrap());
+//! ```
+
+use error::c_string;
 use std::cmp::{Eq, PartialEq};
 use std::convert::TryInto;
 use std::ffi::{CStr, CString};
 use std::fmt;
 use std::hash::{Hash, Hasher};
+use std::ops;
 use std::ptr::null;
+use symbol::symbol_to_string;
 use z3_sys::*;
 use Context;
+use FuncDecl;
 use Sort;
 use Symbol;
-use {Ast, Pattern};
+use {Ast, Pattern, Z3Error};
 
 macro_rules! unop {
-    ( $f:ident, $z3fn:ident ) => {
+    ( $f:ident, $try_f:ident, $z3fn:ident ) => {
         pub fn $f(&self) -> Ast<'ctx> {
-            Ast::new(self.ctx, unsafe {
-                $z3fn(self.ctx.z3_ctx, self.z3_ast)
-            })
-    }
+            self.$try_f().unwrap()
+        }
+
+        pub fn $try_f(&self) -> Result<Ast<'ctx>, Z3Error> {
+            Ast::try_new(self.ctx, unsafe { $z3fn(self.ctx.z3_ctx, self.z3_ast) })
+        }
     };
 }
 
 macro_rules! binop {
-    ( $f:ident, $z3fn:ident ) => {
+    ( $f:ident, $try_f:ident, $z3fn:ident ) => {
         pub fn $f(&self, other: &Ast<'ctx>) -> Ast<'ctx> {
-            Ast::new(self.ctx, unsafe {
+            self.$try_f(other).unwrap()
+        }
+
+        pub fn $try_f(&self, other: &Ast<'ctx>) -> Result<Ast<'ctx>, Z3Error> {
+            Ast::try_new(self.ctx, unsafe {
                 $z3fn(self.ctx.z3_ctx, self.z3_ast, other.z3_ast)
             })
-    }
+        }
     };
 }
 
 macro_rules! binop_bool {
-    ( $f:ident, $z3fn:ident ) => {
+    ( $f:ident, $try_f:ident, $z3fn:ident ) => {
         pub fn $f(&self, other: &Ast<'ctx>, b: bool) -> Ast<'ctx> {
-            Ast::new(self.ctx, unsafe {
+            self.$try_f(other, b).unwrap()
+        }
+
+        pub fn $try_f(&self, other: &Ast<'ctx>, b: bool) -> Result<Ast<'ctx>, Z3Error> {
+            Ast::try_new(self.ctx, unsafe {
                 $z3fn(self.ctx.z3_ctx, self.z3_ast, other.z3_ast, b)
             })
-    }
+        }
     };
 }
 
 macro_rules! trinop {
-    ( $f:ident, $z3fn:ident ) => {
+    ( $f:ident, $try_f:ident, $z3fn:ident ) => {
         pub fn $f(&self, a: &Ast<'ctx>, b: &Ast<'ctx>) -> Ast<'ctx> {
-            Ast::new(self.ctx, unsafe {
+            self.$try_f(a, b).unwrap()
+        }
+
+        pub fn $try_f(&self, a: &Ast<'ctx>, b: &Ast<'ctx>) -> Result<Ast<'ctx>, Z3Error> {
+            Ast::try_new(self.ctx, unsafe {
                 $z3fn(self.ctx.z3_ctx, self.z3_ast, a.z3_ast, b.z3_ast)
             })
-    }
+        }
     };
 }
 
 macro_rules! varop {
-    ( $f:ident, $z3fn:ident ) => {
+    ( $f:ident, $try_f:ident, $z3fn:ident ) => {
         pub fn $f(&self, other: &[&Ast<'ctx>]) -> Ast<'ctx> {
-            Ast::new(self.ctx, unsafe {
+            self.$try_f(other).unwrap()
+        }
+
+        pub fn $try_f(&self, other: &[&Ast<'ctx>]) -> Result<Ast<'ctx>, Z3Error> {
+            Ast::try_new(self.ctx, unsafe {
                 let mut tmp = vec![self.z3_ast];
                 for a in other {
                     tmp.push(a.z3_ast)
@@ -61,7 +110,44 @@ macro_rules! varop {
                 assert!(tmp.len() <= 0xffff_ffff);
                 $z3fn(self.ctx.z3_ctx, tmp.len() as u32, tmp.as_ptr())
             })
-    }
+        }
+    };
+}
+
+macro_rules! rm_unop {
+    ( $f:ident, $try_f:ident, $z3fn:ident ) => {
+        pub fn $f(&self, rm: &Ast<'ctx>) -> Ast<'ctx> {
+            self.$try_f(rm).unwrap()
+        }
+
+        pub fn $try_f(&self, rm: &Ast<'ctx>) -> Result<Ast<'ctx>, Z3Error> {
+            Ast::try_new(self.ctx, unsafe {
+                $z3fn(self.ctx.z3_ctx, rm.z3_ast, self.z3_ast)
+            })
+        }
+    };
+}
+
+macro_rules! rm_binop {
+    ( $f:ident, $try_f:ident, $z3fn:ident ) => {
+        pub fn $f(&self, rm: &Ast<'ctx>, other: &Ast<'ctx>) -> Ast<'ctx> {
+            self.$try_f(rm, other).unwrap()
+        }
+
+        pub fn $try_f(&self, rm: &Ast<'ctx>, other: &Ast<'ctx>) -> Result<Ast<'ctx>, Z3Error> {
+            Ast::try_new(self.ctx, unsafe {
+                $z3fn(self.ctx.z3_ctx, rm.z3_ast, self.z3_ast, other.z3_ast)
+            })
+        }
+    };
+}
+
+macro_rules! rounding_mode {
+    ( $f:ident, $z3fn:ident, $doc:expr ) => {
+        #[doc = $doc]
+        pub fn $f(ctx: &'ctx Context) -> Ast<'ctx> {
+            Ast::new(ctx, unsafe { $z3fn(ctx.z3_ctx) })
+        }
     };
 }
 
@@ -78,6 +164,12 @@ impl<'ctx> Ast<'ctx> {
         }
     }
 
+    /// Wrap the result of a Z3 API call, or report the error of that call.
+    pub(crate) fn try_new(ctx: &'ctx Context, ast: Z3_ast) -> Result<Ast<'ctx>, Z3Error> {
+        ctx.check_error()?;
+        Ok(Ast::new(ctx, ast))
+    }
+
     pub fn translate<'dest_ctx>(&self, dest: &'dest_ctx Context) -> Ast<'dest_ctx> {
         Ast::new(dest, unsafe {
             Z3_translate(self.ctx.z3_ctx, self.z3_ast, dest.z3_ctx)
@@ -154,6 +246,128 @@ impl<'ctx> Ast<'ctx> {
         })
     }
 
+    /// Create a floating-point numeral of sort `sort` from `v`, rounding
+    /// to nearest, ties to even, if `sort` is smaller than
+    /// [`Sort::float64()`](../struct.Sort.html#method.float64).
+    ///
+    /// Panics if `sort` is not a floating-point sort.
+    pub fn from_f64(sort: &Sort<'ctx>, v: f64) -> Ast<'ctx> {
+        Ast::try_from_f64(sort, v).unwrap()
+    }
+
+    pub fn try_from_f64(sort: &Sort<'ctx>, v: f64) -> Result<Ast<'ctx>, Z3Error> {
+        let ctx = sort.ctx;
+        let double = Sort::float64(ctx);
+        let ast = Ast::new(ctx, unsafe {
+            Z3_mk_fpa_numeral_double(ctx.z3_ctx, v, double.z3_sort)
+        });
+        if *sort == double {
+            return Ok(ast);
+        }
+        // Z3 does not round to nearest when creating a numeral of a
+        // smaller sort directly.
+        let rm = Ast::round_nearest_ties_to_even(ctx);
+        Ok(ast.try_fp_to_fp(&rm, sort)?.simplify())
+    }
+
+    /// Create the floating-point NaN of sort `sort`.
+    pub fn fp_nan(sort: &Sort<'ctx>) -> Ast<'ctx> {
+        Ast::new(sort.ctx, unsafe {
+            Z3_mk_fpa_nan(sort.ctx.z3_ctx, sort.z3_sort)
+        })
+    }
+
+    /// Create the positive or negative floating-point infinity of sort
+    /// `sort`.
+    pub fn fp_inf(sort: &Sort<'ctx>, negative: bool) -> Ast<'ctx> {
+        Ast::new(sort.ctx, unsafe {
+            Z3_mk_fpa_inf(sort.ctx.z3_ctx, sort.z3_sort, negative)
+        })
+    }
+
+    /// Create the positive or negative floating-point zero of sort `sort`.
+    pub fn fp_zero(sort: &Sort<'ctx>, negative: bool) -> Ast<'ctx> {
+        Ast::new(sort.ctx, unsafe {
+            Z3_mk_fpa_zero(sort.ctx.z3_ctx, sort.z3_sort, negative)
+        })
+    }
+
+    rounding_mode!(
+        round_nearest_ties_to_even,
+        Z3_mk_fpa_round_nearest_ties_to_even,
+        "Create the rounding mode `RNE`: round to nearest, ties to even."
+    );
+    rounding_mode!(
+        round_nearest_ties_to_away,
+        Z3_mk_fpa_round_nearest_ties_to_away,
+        "Create the rounding mode `RNA`: round to nearest, ties away from zero."
+    );
+    rounding_mode!(
+        round_toward_positive,
+        Z3_mk_fpa_round_toward_positive,
+        "Create the rounding mode `RTP`: round toward positive infinity."
+    );
+    rounding_mode!(
+        round_toward_negative,
+        Z3_mk_fpa_round_toward_negative,
+        "Create the rounding mode `RTN`: round toward negative infinity."
+    );
+    rounding_mode!(
+        round_toward_zero,
+        Z3_mk_fpa_round_toward_zero,
+        "Create the rounding mode `RTZ`: round toward zero."
+    );
+
+    /// Create a string literal. Escape sequences such as `\u{e9}` are
+    /// interpreted as in SMT-LIB2.
+    ///
+    /// Panics if `s` contains a NUL character.
+    pub fn from_string(ctx: &'ctx Context, s: &str) -> Ast<'ctx> {
+        let s = CString::new(s).unwrap();
+        Ast::new(ctx, unsafe { Z3_mk_string(ctx.z3_ctx, s.as_ptr()) })
+    }
+
+    /// Create the empty sequence of the sequence sort `sort`.
+    pub fn seq_empty(sort: &Sort<'ctx>) -> Ast<'ctx> {
+        Ast::new(sort.ctx, unsafe {
+            Z3_mk_seq_empty(sort.ctx.z3_ctx, sort.z3_sort)
+        })
+    }
+
+    /// Create the regular expression of the regular expression sort `sort`
+    /// that matches nothing.
+    pub fn re_empty(sort: &Sort<'ctx>) -> Ast<'ctx> {
+        Ast::new(sort.ctx, unsafe {
+            Z3_mk_re_empty(sort.ctx.z3_ctx, sort.z3_sort)
+        })
+    }
+
+    /// Create the regular expression of the regular expression sort `sort`
+    /// that matches every sequence.
+    pub fn re_full(sort: &Sort<'ctx>) -> Ast<'ctx> {
+        Ast::new(sort.ctx, unsafe {
+            Z3_mk_re_full(sort.ctx.z3_ctx, sort.z3_sort)
+        })
+    }
+
+    /// Create a numeral of the given sort from its decimal representation,
+    /// e.g. `"-42"` or `"3.25"`. Numerals of real sort may also be written
+    /// as a fraction such as `"1/3"`.
+    ///
+    /// Unlike [`from_i64`](#method.from_i64) this is not limited to 64 bits.
+    ///
+    /// Panics if `s` is not a valid numeral of sort `sort`.
+    pub fn from_str_numeral(sort: &Sort<'ctx>, s: &str) -> Ast<'ctx> {
+        Ast::try_from_str_numeral(sort, s).unwrap()
+    }
+
+    pub fn try_from_str_numeral(sort: &Sort<'ctx>, s: &str) -> Result<Ast<'ctx>, Z3Error> {
+        let ss = c_string(s, Z3_INVALID_ARG)?;
+        Ast::try_new(sort.ctx, unsafe {
+            Z3_mk_numeral(sort.ctx.z3_ctx, ss.as_ptr(), sort.z3_sort)
+        })
+    }
+
     pub fn as_bool(&self) -> Option<bool> {
         unsafe {
             match Z3_get_bool_value(self.ctx.z3_ctx, self.z3_ast) {
@@ -228,13 +442,69 @@ impl<'ctx> Ast<'ctx> {
         }
     }
 
+    /// Return the value of a string literal, or `None` if this is not a
+    /// string literal. Characters that are not printable ASCII are
+    /// escaped as in SMT-LIB2.
+    ///
+    /// ```
+    /// # use z3::{Ast, Config, Context, SatResult, Solver, Sort, Symbol};
+    /// let cfg = Config::new();
+    /// let ctx = Context::new(&cfg);
+    /// let s = Ast::new_const(&Symbol::from_string(&ctx, "s"), &Sort::string(&ctx));
+    /// let solver = Solver::new(&ctx);
+    /// solver.assert(&s.seq_concat(&[&Ast::from_string(&ctx, "!")])._eq(&Ast::from_string(&ctx, "hi!")));
+    /// assert_eq!(solver.check(), SatResult::Sat);
+    ///
+    /// let value = solver.get_model().eval(&s).unwrap();
+    /// assert_eq!(value.as_string(), Some("hi".to_owned()));
+    /// ```
+    pub fn as_string(&self) -> Option<String> {
+        if !unsafe { Z3_is_string(self.ctx.z3_ctx, self.z3_ast) } {
+            return None;
+        }
+        let p = unsafe { Z3_get_string(self.ctx.z3_ctx, self.z3_ast) };
+        if p.is_null() {
+            return None;
+        }
+        Some(unsafe { CStr::from_ptr(p) }.to_string_lossy().into_owned())
+    }
+
+    /// Return the value of an integer, real or floating-point numeral as
+    /// the closest `f64`, or `None` if this is not such a numeral.
+    ///
+    /// Floating-point numerals of other sorts than
+    /// [`Sort::float64()`](../struct.Sort.html#method.float64) are rounded
+    /// to nearest, ties to even.
+    pub fn as_f64(&self) -> Option<f64> {
+        if !self.is_numeral() {
+            return None;
+        }
+        match self.sort().kind() {
+            Z3_INT_SORT | Z3_REAL_SORT => {
+                Some(unsafe { Z3_get_numeral_double(self.ctx.z3_ctx, self.z3_ast) })
+            }
+            Z3_FLOATING_POINT_SORT => {
+                if unsafe { Z3_fpa_is_numeral_nan(self.ctx.z3_ctx, self.z3_ast) } {
+                    return Some(f64::NAN);
+                }
+                let rm = Ast::round_nearest_ties_to_even(self.ctx);
+                self.fp_to_fp(&rm, &Sort::float64(self.ctx))
+                    .fp_to_ieee_bv()
+                    .simplify()
+                    .as_u64()
+                    .map(f64::from_bits)
+            }
+            _ => None,
+        }
+    }
+
     /// Create a bit vector from an integer
     ///
     /// The bit vector has width `n`.
     ///
     /// # Examples
     /// ```
-    /// # use z3::{Config, Context, Solver};
+    /// # use z3::{Config, Context, SatResult, Solver};
     /// # let cfg = Config::new();
     /// # let ctx = Context::new(&cfg);
     /// # let solver = Solver::new(&ctx);
@@ -243,13 +513,17 @@ impl<'ctx> Ast<'ctx> {
     ///
     /// let x = i.int2bv(32).bv2int(true);
     ///
-    /// assert!(solver.check());
+    /// assert_eq!(solver.check(), SatResult::Sat);
     /// let model = solver.get_model();
     ///
     /// assert_eq!(-3, model.eval(&x).unwrap().as_i64().unwrap());
     /// ```
     pub fn int2bv(&self, n: u64) -> Ast<'ctx> {
-        Ast::new(self.ctx, unsafe {
+        self.try_int2bv(n).unwrap()
+    }
+
+    pub fn try_int2bv(&self, n: u64) -> Result<Ast<'ctx>, Z3Error> {
+        Ast::try_new(self.ctx, unsafe {
             Z3_mk_int2bv(self.ctx.z3_ctx, n.try_into().unwrap(), self.z3_ast)
         })
     }
@@ -260,7 +534,7 @@ impl<'ctx> Ast<'ctx> {
     ///
     /// # Examples
     /// ```
-    /// # use z3::{Config, Context, Solver};
+    /// # use z3::{Config, Context, SatResult, Solver};
     /// # let cfg = Config::new();
     /// # let ctx = Context::new(&cfg);
     /// # let solver = Solver::new(&ctx);
@@ -269,81 +543,88 @@ impl<'ctx> Ast<'ctx> {
     ///
     /// let x = i.int2bv(32).bv2int(true);
     ///
-    /// assert!(solver.check());
+    /// assert_eq!(solver.check(), SatResult::Sat);
     /// let model = solver.get_model();
     ///
     /// assert_eq!(-3, model.eval(&x).unwrap().as_i64().unwrap());
     /// ```
     pub fn bv2int(&self, signed: bool) -> Ast<'ctx> {
-        Ast::new(self.ctx, unsafe {
+        self.try_bv2int(signed).unwrap()
+    }
+
+    pub fn try_bv2int(&self, signed: bool) -> Result<Ast<'ctx>, Z3Error> {
+        Ast::try_new(self.ctx, unsafe {
             Z3_mk_bv2int(self.ctx.z3_ctx, self.z3_ast, signed)
         })
     }
 
-    varop!(distinct, Z3_mk_distinct);
+    varop!(distinct, try_distinct, Z3_mk_distinct);
 
     // Boolean ops
-    trinop!(ite, Z3_mk_ite);
-    binop!(iff, Z3_mk_iff);
-    binop!(implies, Z3_mk_implies);
-    binop!(xor, Z3_mk_xor);
-    varop!(and, Z3_mk_and);
-    varop!(or, Z3_mk_or);
-    varop!(add, Z3_mk_add);
-    varop!(sub, Z3_mk_sub);
-    varop!(mul, Z3_mk_mul);
-    unop!(not, Z3_mk_not);
+    trinop!(ite, try_ite, Z3_mk_ite);
+    binop!(iff, try_iff, Z3_mk_iff);
+    binop!(implies, try_implies, Z3_mk_implies);
+    binop!(xor, try_xor, Z3_mk_xor);
+    varop!(and, try_and, Z3_mk_and);
+    varop!(or, try_or, Z3_mk_or);
+    varop!(add, try_add, Z3_mk_add);
+    varop!(sub, try_sub, Z3_mk_sub);
+    varop!(mul, try_mul, Z3_mk_mul);
+    unop!(not, try_not, Z3_mk_not);
 
     // Numeric ops
-    binop!(div, Z3_mk_div);
-    binop!(rem, Z3_mk_rem);
-    binop!(modulo, Z3_mk_mod);
-    binop!(power, Z3_mk_power);
-    unop!(minus, Z3_mk_unary_minus);
-    binop!(lt, Z3_mk_lt);
-    binop!(le, Z3_mk_le);
-    binop!(_eq, Z3_mk_eq);
-    binop!(ge, Z3_mk_ge);
-    binop!(gt, Z3_mk_gt);
-    unop!(int2real, Z3_mk_int2real);
-    unop!(real2int, Z3_mk_real2int);
-    unop!(is_int, Z3_mk_is_int);
+    binop!(div, try_div, Z3_mk_div);
+    binop!(rem, try_rem, Z3_mk_rem);
+    binop!(modulo, try_modulo, Z3_mk_mod);
+    binop!(power, try_power, Z3_mk_power);
+    unop!(minus, try_minus, Z3_mk_unary_minus);
+    binop!(lt, try_lt, Z3_mk_lt);
+    binop!(le, try_le, Z3_mk_le);
+    binop!(_eq, try_eq, Z3_mk_eq);
+    binop!(ge, try_ge, Z3_mk_ge);
+    binop!(gt, try_gt, Z3_mk_gt);
+    unop!(int2real, try_int2real, Z3_mk_int2real);
+    unop!(real2int, try_real2int, Z3_mk_real2int);
+    unop!(is_int, try_is_int, Z3_mk_is_int);
 
     // Bitvector ops
-    unop!(bvnot, Z3_mk_bvnot);
-    unop!(bvneg, Z3_mk_bvneg);
-    unop!(bvredand, Z3_mk_bvredand);
-    unop!(bvredor, Z3_mk_bvredor);
-    binop!(bvand, Z3_mk_bvand);
-    binop!(bvor, Z3_mk_bvor);
-    binop!(bvxor, Z3_mk_bvxor);
-    binop!(bvnand, Z3_mk_bvnand);
-    binop!(bvnor, Z3_mk_bvnor);
-    binop!(bvxnor, Z3_mk_bvxnor);
-    binop!(bvadd, Z3_mk_bvadd);
-    binop!(bvsub, Z3_mk_bvsub);
-    binop!(bvmul, Z3_mk_bvmul);
-    binop!(bvudiv, Z3_mk_bvudiv);
-    binop!(bvsdiv, Z3_mk_bvsdiv);
-    binop!(bvurem, Z3_mk_bvurem);
-    binop!(bvsrem, Z3_mk_bvsrem);
-    binop!(bvsmod, Z3_mk_bvsmod);
-    binop!(bvult, Z3_mk_bvult);
-    binop!(bvslt, Z3_mk_bvslt);
-    binop!(bvule, Z3_mk_bvule);
-    binop!(bvsle, Z3_mk_bvsle);
-    binop!(bvuge, Z3_mk_bvuge);
-    binop!(bvsge, Z3_mk_bvsge);
-    binop!(bvugt, Z3_mk_bvugt);
-    binop!(bvsgt, Z3_mk_bvsgt);
-    binop!(concat, Z3_mk_concat);
-    binop!(bvshl, Z3_mk_bvshl);
-    binop!(bvlshr, Z3_mk_bvlshr);
-    binop!(bvashr, Z3_mk_bvashr);
-    binop!(bvrotl, Z3_mk_ext_rotate_left);
-    binop!(bvrotr, Z3_mk_ext_rotate_right);
+    unop!(bvnot, try_bvnot, Z3_mk_bvnot);
+    unop!(bvneg, try_bvneg, Z3_mk_bvneg);
+    unop!(bvredand, try_bvredand, Z3_mk_bvredand);
+    unop!(bvredor, try_bvredor, Z3_mk_bvredor);
+    binop!(bvand, try_bvand, Z3_mk_bvand);
+    binop!(bvor, try_bvor, Z3_mk_bvor);
+    binop!(bvxor, try_bvxor, Z3_mk_bvxor);
+    binop!(bvnand, try_bvnand, Z3_mk_bvnand);
+    binop!(bvnor, try_bvnor, Z3_mk_bvnor);
+    binop!(bvxnor, try_bvxnor, Z3_mk_bvxnor);
+    binop!(bvadd, try_bvadd, Z3_mk_bvadd);
+    binop!(bvsub, try_bvsub, Z3_mk_bvsub);
+    binop!(bvmul, try_bvmul, Z3_mk_bvmul);
+    binop!(bvudiv, try_bvudiv, Z3_mk_bvudiv);
+    binop!(bvsdiv, try_bvsdiv, Z3_mk_bvsdiv);
+    binop!(bvurem, try_bvurem, Z3_mk_bvurem);
+    binop!(bvsrem, try_bvsrem, Z3_mk_bvsrem);
+    binop!(bvsmod, try_bvsmod, Z3_mk_bvsmod);
+    binop!(bvult, try_bvult, Z3_mk_bvult);
+    binop!(bvslt, try_bvslt, Z3_mk_bvslt);
+    binop!(bvule, try_bvule, Z3_mk_bvule);
+    binop!(bvsle, try_bvsle, Z3_mk_bvsle);
+    binop!(bvuge, try_bvuge, Z3_mk_bvuge);
+    binop!(bvsge, try_bvsge, Z3_mk_bvsge);
+    binop!(bvugt, try_bvugt, Z3_mk_bvugt);
+    binop!(bvsgt, try_bvsgt, Z3_mk_bvsgt);
+    binop!(concat, try_concat, Z3_mk_concat);
+    binop!(bvshl, try_bvshl, Z3_mk_bvshl);
+    binop!(bvlshr, try_bvlshr, Z3_mk_bvlshr);
+    binop!(bvashr, try_bvashr, Z3_mk_bvashr);
+    binop!(bvrotl, try_bvrotl, Z3_mk_ext_rotate_left);
+    binop!(bvrotr, try_bvrotr, Z3_mk_ext_rotate_right);
     pub fn bvextract(&self, high: usize, low: usize) -> Ast<'ctx> {
-        Ast::new(self.ctx, unsafe {
+        self.try_bvextract(high, low).unwrap()
+    }
+    pub fn try_bvextract(&self, high: usize, low: usize) -> Result<Ast<'ctx>, Z3Error> {
+        Ast::try_new(self.ctx, unsafe {
             Z3_mk_extract(
                 self.ctx.z3_ctx,
                 high.try_into().unwrap(),
@@ -353,43 +634,262 @@ impl<'ctx> Ast<'ctx> {
         })
     }
     pub fn bvrepeat(&self, n: usize) -> Ast<'ctx> {
-        Ast::new(self.ctx, unsafe {
+        self.try_bvrepeat(n).unwrap()
+    }
+    pub fn try_bvrepeat(&self, n: usize) -> Result<Ast<'ctx>, Z3Error> {
+        Ast::try_new(self.ctx, unsafe {
             Z3_mk_repeat(self.ctx.z3_ctx, n.try_into().unwrap(), self.z3_ast)
         })
     }
     pub fn bvsignextend(&self, n: usize) -> Ast<'ctx> {
-        Ast::new(self.ctx, unsafe {
+        self.try_bvsignextend(n).unwrap()
+    }
+    pub fn try_bvsignextend(&self, n: usize) -> Result<Ast<'ctx>, Z3Error> {
+        Ast::try_new(self.ctx, unsafe {
             Z3_mk_sign_ext(self.ctx.z3_ctx, n.try_into().unwrap(), self.z3_ast)
         })
     }
     pub fn bvzeroextend(&self, n: usize) -> Ast<'ctx> {
-        Ast::new(self.ctx, unsafe {
+        self.try_bvzeroextend(n).unwrap()
+    }
+    pub fn try_bvzeroextend(&self, n: usize) -> Result<Ast<'ctx>, Z3Error> {
+        Ast::try_new(self.ctx, unsafe {
             Z3_mk_zero_ext(self.ctx.z3_ctx, n.try_into().unwrap(), self.z3_ast)
         })
     }
 
     // overflow checks
-    binop_bool!(bvadd_no_overflow, Z3_mk_bvadd_no_overflow);
-    binop!(bvadd_no_underflow, Z3_mk_bvadd_no_underflow);
-    binop!(bvsub_no_overflow, Z3_mk_bvsub_no_overflow);
-    binop_bool!(bvsub_no_underflow, Z3_mk_bvsub_no_underflow);
-    binop!(bvsdiv_no_overflow, Z3_mk_bvsdiv_no_overflow);
-    unop!(bvneg_no_overflow, Z3_mk_bvneg_no_overflow);
-    binop_bool!(bvmul_no_overflow, Z3_mk_bvmul_no_overflow);
-    binop!(bvmul_no_underflow, Z3_mk_bvmul_no_underflow);
+    binop_bool!(
+        bvadd_no_overflow,
+        try_bvadd_no_overflow,
+        Z3_mk_bvadd_no_overflow
+    );
+    binop!(
+        bvadd_no_underflow,
+        try_bvadd_no_underflow,
+        Z3_mk_bvadd_no_underflow
+    );
+    binop!(
+        bvsub_no_overflow,
+        try_bvsub_no_overflow,
+        Z3_mk_bvsub_no_overflow
+    );
+    binop_bool!(
+        bvsub_no_underflow,
+        try_bvsub_no_underflow,
+        Z3_mk_bvsub_no_underflow
+    );
+    binop!(
+        bvsdiv_no_overflow,
+        try_bvsdiv_no_overflow,
+        Z3_mk_bvsdiv_no_overflow
+    );
+    unop!(
+        bvneg_no_overflow,
+        try_bvneg_no_overflow,
+        Z3_mk_bvneg_no_overflow
+    );
+    binop_bool!(
+        bvmul_no_overflow,
+        try_bvmul_no_overflow,
+        Z3_mk_bvmul_no_overflow
+    );
+    binop!(
+        bvmul_no_underflow,
+        try_bvmul_no_underflow,
+        Z3_mk_bvmul_no_underflow
+    );
 
     // Array ops
-    binop!(select, Z3_mk_select);
-    trinop!(store, Z3_mk_store);
+    binop!(select, try_select, Z3_mk_select);
+    trinop!(store, try_store, Z3_mk_store);
 
     // Set ops
-    binop!(set_add, Z3_mk_set_add);
-    binop!(set_del, Z3_mk_set_del);
-    varop!(set_union, Z3_mk_set_union);
-    varop!(set_intersect, Z3_mk_set_intersect);
-    binop!(set_member, Z3_mk_set_member);
-    binop!(set_subset, Z3_mk_set_subset);
-    unop!(set_complement, Z3_mk_set_complement);
+    binop!(set_add, try_set_add, Z3_mk_set_add);
+    binop!(set_del, try_set_del, Z3_mk_set_del);
+    varop!(set_union, try_set_union, Z3_mk_set_union);
+    varop!(set_intersect, try_set_intersect, Z3_mk_set_intersect);
+    binop!(set_member, try_set_member, Z3_mk_set_member);
+    binop!(set_subset, try_set_subset, Z3_mk_set_subset);
+    unop!(set_complement, try_set_complement, Z3_mk_set_complement);
+
+    // Floating-point ops
+    unop!(fp_abs, try_fp_abs, Z3_mk_fpa_abs);
+    unop!(fp_neg, try_fp_neg, Z3_mk_fpa_neg);
+    rm_binop!(fp_add, try_fp_add, Z3_mk_fpa_add);
+    rm_binop!(fp_sub, try_fp_sub, Z3_mk_fpa_sub);
+    rm_binop!(fp_mul, try_fp_mul, Z3_mk_fpa_mul);
+    rm_binop!(fp_div, try_fp_div, Z3_mk_fpa_div);
+    rm_unop!(fp_sqrt, try_fp_sqrt, Z3_mk_fpa_sqrt);
+    rm_unop!(
+        fp_round_to_integral,
+        try_fp_round_to_integral,
+        Z3_mk_fpa_round_to_integral
+    );
+    binop!(fp_rem, try_fp_rem, Z3_mk_fpa_rem);
+    binop!(fp_min, try_fp_min, Z3_mk_fpa_min);
+    binop!(fp_max, try_fp_max, Z3_mk_fpa_max);
+    binop!(fp_lt, try_fp_lt, Z3_mk_fpa_lt);
+    binop!(fp_le, try_fp_le, Z3_mk_fpa_leq);
+    binop!(fp_eq, try_fp_eq, Z3_mk_fpa_eq);
+    binop!(fp_ge, try_fp_ge, Z3_mk_fpa_geq);
+    binop!(fp_gt, try_fp_gt, Z3_mk_fpa_gt);
+    unop!(fp_is_normal, try_fp_is_normal, Z3_mk_fpa_is_normal);
+    unop!(fp_is_subnormal, try_fp_is_subnormal, Z3_mk_fpa_is_subnormal);
+    unop!(fp_is_zero, try_fp_is_zero, Z3_mk_fpa_is_zero);
+    unop!(fp_is_infinite, try_fp_is_infinite, Z3_mk_fpa_is_infinite);
+    unop!(fp_is_nan, try_fp_is_nan, Z3_mk_fpa_is_nan);
+    unop!(fp_is_negative, try_fp_is_negative, Z3_mk_fpa_is_negative);
+    unop!(fp_is_positive, try_fp_is_positive, Z3_mk_fpa_is_positive);
+    unop!(fp_to_real, try_fp_to_real, Z3_mk_fpa_to_real);
+    unop!(fp_to_ieee_bv, try_fp_to_ieee_bv, Z3_mk_fpa_to_ieee_bv);
+
+    /// Compute `self * a + b` with a single rounding.
+    pub fn fp_fma(&self, rm: &Ast<'ctx>, a: &Ast<'ctx>, b: &Ast<'ctx>) -> Ast<'ctx> {
+        self.try_fp_fma(rm, a, b).unwrap()
+    }
+
+    pub fn try_fp_fma(
+        &self,
+        rm: &Ast<'ctx>,
+        a: &Ast<'ctx>,
+        b: &Ast<'ctx>,
+    ) -> Result<Ast<'ctx>, Z3Error> {
+        Ast::try_new(self.ctx, unsafe {
+            Z3_mk_fpa_fma(self.ctx.z3_ctx, rm.z3_ast, self.z3_ast, a.z3_ast, b.z3_ast)
+        })
+    }
+
+    /// Convert a floating-point term to a bit vector of width `sz`, rounding
+    /// it to an unsigned integer. The result is unspecified if the value
+    /// does not fit.
+    pub fn fp_to_ubv(&self, rm: &Ast<'ctx>, sz: u32) -> Ast<'ctx> {
+        self.try_fp_to_ubv(rm, sz).unwrap()
+    }
+
+    pub fn try_fp_to_ubv(&self, rm: &Ast<'ctx>, sz: u32) -> Result<Ast<'ctx>, Z3Error> {
+        Ast::try_new(self.ctx, unsafe {
+            Z3_mk_fpa_to_ubv(self.ctx.z3_ctx, rm.z3_ast, self.z3_ast, sz)
+        })
+    }
+
+    /// Convert a floating-point term to a bit vector of width `sz`, rounding
+    /// it to a signed integer. The result is unspecified if the value does
+    /// not fit.
+    pub fn fp_to_sbv(&self, rm: &Ast<'ctx>, sz: u32) -> Ast<'ctx> {
+        self.try_fp_to_sbv(rm, sz).unwrap()
+    }
+
+    pub fn try_fp_to_sbv(&self, rm: &Ast<'ctx>, sz: u32) -> Result<Ast<'ctx>, Z3Error> {
+        Ast::try_new(self.ctx, unsafe {
+            Z3_mk_fpa_to_sbv(self.ctx.z3_ctx, rm.z3_ast, self.z3_ast, sz)
+        })
+    }
+
+    /// Convert a floating-point term to the floating-point sort `sort`.
+    pub fn fp_to_fp(&self, rm: &Ast<'ctx>, sort: &Sort<'ctx>) -> Ast<'ctx> {
+        self.try_fp_to_fp(rm, sort).unwrap()
+    }
+
+    pub fn try_fp_to_fp(&self, rm: &Ast<'ctx>, sort: &Sort<'ctx>) -> Result<Ast<'ctx>, Z3Error> {
+        Ast::try_new(self.ctx, unsafe {
+            Z3_mk_fpa_to_fp_float(self.ctx.z3_ctx, rm.z3_ast, self.z3_ast, sort.z3_sort)
+        })
+    }
+
+    /// Reinterpret a bit vector as an IEEE 754 value of the floating-point
+    /// sort `sort`. This is the inverse of
+    /// [`Ast::fp_to_ieee_bv()`](#method.fp_to_ieee_bv).
+    pub fn fp_from_ieee_bv(&self, sort: &Sort<'ctx>) -> Ast<'ctx> {
+        self.try_fp_from_ieee_bv(sort).unwrap()
+    }
+
+    pub fn try_fp_from_ieee_bv(&self, sort: &Sort<'ctx>) -> Result<Ast<'ctx>, Z3Error> {
+        Ast::try_new(self.ctx, unsafe {
+            Z3_mk_fpa_to_fp_bv(self.ctx.z3_ctx, self.z3_ast, sort.z3_sort)
+        })
+    }
+
+    /// Convert a real term to the floating-point sort `sort`.
+    pub fn real_to_fp(&self, rm: &Ast<'ctx>, sort: &Sort<'ctx>) -> Ast<'ctx> {
+        self.try_real_to_fp(rm, sort).unwrap()
+    }
+
+    pub fn try_real_to_fp(&self, rm: &Ast<'ctx>, sort: &Sort<'ctx>) -> Result<Ast<'ctx>, Z3Error> {
+        Ast::try_new(self.ctx, unsafe {
+            Z3_mk_fpa_to_fp_real(self.ctx.z3_ctx, rm.z3_ast, self.z3_ast, sort.z3_sort)
+        })
+    }
+
+    /// Convert a bit vector, read as a signed integer, to the
+    /// floating-point sort `sort`.
+    pub fn sbv_to_fp(&self, rm: &Ast<'ctx>, sort: &Sort<'ctx>) -> Ast<'ctx> {
+        self.try_sbv_to_fp(rm, sort).unwrap()
+    }
+
+    pub fn try_sbv_to_fp(&self, rm: &Ast<'ctx>, sort: &Sort<'ctx>) -> Result<Ast<'ctx>, Z3Error> {
+        Ast::try_new(self.ctx, unsafe {
+            Z3_mk_fpa_to_fp_signed(self.ctx.z3_ctx, rm.z3_ast, self.z3_ast, sort.z3_sort)
+        })
+    }
+
+    /// Convert a bit vector, read as an unsigned integer, to the
+    /// floating-point sort `sort`.
+    pub fn ubv_to_fp(&self, rm: &Ast<'ctx>, sort: &Sort<'ctx>) -> Ast<'ctx> {
+        self.try_ubv_to_fp(rm, sort).unwrap()
+    }
+
+    pub fn try_ubv_to_fp(&self, rm: &Ast<'ctx>, sort: &Sort<'ctx>) -> Result<Ast<'ctx>, Z3Error> {
+        Ast::try_new(self.ctx, unsafe {
+            Z3_mk_fpa_to_fp_unsigned(self.ctx.z3_ctx, rm.z3_ast, self.z3_ast, sort.z3_sort)
+        })
+    }
+
+    // Sequence ops
+    unop!(seq_unit, try_seq_unit, Z3_mk_seq_unit);
+    varop!(seq_concat, try_seq_concat, Z3_mk_seq_concat);
+    unop!(seq_length, try_seq_length, Z3_mk_seq_length);
+    binop!(seq_contains, try_seq_contains, Z3_mk_seq_contains);
+    binop!(seq_prefix_of, try_seq_prefix_of, Z3_mk_seq_prefix);
+    binop!(seq_suffix_of, try_seq_suffix_of, Z3_mk_seq_suffix);
+    binop!(seq_at, try_seq_at, Z3_mk_seq_at);
+    binop!(seq_nth, try_seq_nth, Z3_mk_seq_nth);
+    trinop!(seq_substr, try_seq_substr, Z3_mk_seq_extract);
+    trinop!(seq_replace, try_seq_replace, Z3_mk_seq_replace);
+    trinop!(seq_index_of, try_seq_index_of, Z3_mk_seq_index);
+    binop!(
+        seq_last_index_of,
+        try_seq_last_index_of,
+        Z3_mk_seq_last_index
+    );
+    binop!(str_lt, try_str_lt, Z3_mk_str_lt);
+    binop!(str_le, try_str_le, Z3_mk_str_le);
+    unop!(str_to_int, try_str_to_int, Z3_mk_str_to_int);
+    unop!(int_to_str, try_int_to_str, Z3_mk_int_to_str);
+
+    // Regular expression ops
+    unop!(seq_to_re, try_seq_to_re, Z3_mk_seq_to_re);
+    binop!(seq_in_re, try_seq_in_re, Z3_mk_seq_in_re);
+    unop!(re_star, try_re_star, Z3_mk_re_star);
+    unop!(re_plus, try_re_plus, Z3_mk_re_plus);
+    unop!(re_option, try_re_option, Z3_mk_re_option);
+    unop!(re_complement, try_re_complement, Z3_mk_re_complement);
+    varop!(re_union, try_re_union, Z3_mk_re_union);
+    varop!(re_concat, try_re_concat, Z3_mk_re_concat);
+    varop!(re_intersect, try_re_intersect, Z3_mk_re_intersect);
+    binop!(re_range, try_re_range, Z3_mk_re_range);
+
+    /// Create a regular expression that matches between `lo` and `hi`
+    /// repetitions of `self`, or at least `lo` repetitions if `hi` is zero.
+    pub fn re_loop(&self, lo: u32, hi: u32) -> Ast<'ctx> {
+        self.try_re_loop(lo, hi).unwrap()
+    }
+
+    pub fn try_re_loop(&self, lo: u32, hi: u32) -> Result<Ast<'ctx>, Z3Error> {
+        Ast::try_new(self.ctx, unsafe {
+            Z3_mk_re_loop(self.ctx.z3_ctx, self.z3_ast, lo, hi)
+        })
+    }
 
     // pseudoboolean ops
     pub fn pb_le(&self, other: &[&Ast<'ctx>], coeffs: Vec<i32>, k: i32) -> Ast<'ctx> {
@@ -507,14 +1007,164 @@ impl<'ctx> Ast<'ctx> {
     }
 
     pub fn sort(&self) -> Sort<'ctx> {
-        Sort {
-            ctx: self.ctx,
-            z3_sort: unsafe {
-                let s = Z3_get_sort(self.ctx.z3_ctx, self.z3_ast);
-                Z3_inc_ref(self.ctx.z3_ctx, Z3_sort_to_ast(self.ctx.z3_ctx, s));
-                s
-            },
+        unsafe { Sort::from_raw(self.ctx, Z3_get_sort(self.ctx.z3_ctx, self.z3_ast)) }
+    }
+
+    /// Return the kind of this term: an application, a numeral, a bound
+    /// variable or a quantifier.
+    pub fn kind(&self) -> Z3_ast_kind {
+        unsafe { Z3_get_ast_kind(self.ctx.z3_ctx, self.z3_ast) }
+    }
+
+    /// Return `true` if this term is a function application. Constants and
+    /// numerals are applications with no arguments.
+    pub fn is_app(&self) -> bool {
+        unsafe { Z3_is_app(self.ctx.z3_ctx, self.z3_ast) }
+    }
+
+    /// Return `true` if this term is a numeral.
+    pub fn is_numeral(&self) -> bool {
+        unsafe { Z3_is_numeral_ast(self.ctx.z3_ctx, self.z3_ast) }
+    }
+
+    /// Return the value of a numeral as a decimal string, or as a fraction
+    /// `num/den` for non-integral reals. Unlike [`as_i64`](#method.as_i64)
+    /// this works for numerals of any size.
+    pub fn as_numeral_string(&self) -> Option<String> {
+        if !self.is_numeral() {
+            return None;
+        }
+        let p = unsafe { Z3_get_numeral_string(self.ctx.z3_ctx, self.z3_ast) };
+        if p.is_null() {
+            return None;
+        }
+        unsafe { CStr::from_ptr(p) }
+            .to_str()
+            .ok()
+            .map(|s| s.to_owned())
+    }
+
+    /// Return the value of a numeral in decimal notation with at most
+    /// `precision` digits after the decimal point. A trailing `?` marks a
+    /// truncated value.
+    ///
+    /// This also works for the irrational algebraic numbers that appear in
+    /// models of nonlinear real arithmetic.
+    pub fn as_decimal_string(&self, precision: u32) -> Option<String> {
+        let is_algebraic = unsafe { Z3_is_algebraic_number(self.ctx.z3_ctx, self.z3_ast) };
+        if !self.is_numeral() && !is_algebraic {
+            return None;
+        }
+        let p = unsafe { Z3_get_numeral_decimal_string(self.ctx.z3_ctx, self.z3_ast, precision) };
+        if p.is_null() {
+            return None;
+        }
+        unsafe { CStr::from_ptr(p) }
+            .to_str()
+            .ok()
+            .map(|s| s.to_owned())
+    }
+
+    /// Return the function declaration of an application.
+    ///
+    /// Panics if this term is not an application; see
+    /// [`is_app`](#method.is_app).
+    pub fn decl(&self) -> FuncDecl<'ctx> {
+        assert!(self.is_app());
+        unsafe {
+            let app = Z3_to_app(self.ctx.z3_ctx, self.z3_ast);
+            FuncDecl::from_raw(self.ctx, Z3_get_app_decl(self.ctx.z3_ctx, app))
+        }
+    }
+
+    /// Return the number of arguments of an application, or zero for any
+    /// other kind of term.
+    pub fn num_children(&self) -> usize {
+        if !self.is_app() {
+            return 0;
         }
+        unsafe {
+            let app = Z3_to_app(self.ctx.z3_ctx, self.z3_ast);
+            Z3_get_app_num_args(self.ctx.z3_ctx, app) as usize
+        }
+    }
+
+    /// Return the `i`-th argument of an application.
+    ///
+    /// Panics if `i` is out of range; see
+    /// [`num_children`](#method.num_children).
+    pub fn nth_child(&self, i: usize) -> Ast<'ctx> {
+        assert!(i < self.num_children());
+        unsafe {
+            let app = Z3_to_app(self.ctx.z3_ctx, self.z3_ast);
+            Ast::new(
+                self.ctx,
+                Z3_get_app_arg(self.ctx.z3_ctx, app, i.try_into().unwrap()),
+            )
+        }
+    }
+
+    /// Return the arguments of an application, or an empty vector for any
+    /// other kind of term.
+    pub fn children(&self) -> Vec<Ast<'ctx>> {
+        (0..self.num_children())
+            .map(|i| self.nth_child(i))
+            .collect()
+    }
+
+    /// Return the de Bruijn index of a bound variable, or `None` if this term
+    /// is not a bound variable.
+    ///
+    /// Bound variables only occur in the body of a quantifier; see
+    /// [`quantifier_body`](#method.quantifier_body).
+    pub fn var_index(&self) -> Option<u32> {
+        if self.kind() != Z3_VAR_AST {
+            return None;
+        }
+        Some(unsafe { Z3_get_index_value(self.ctx.z3_ctx, self.z3_ast) })
+    }
+
+    /// Return `true` if this term is a universal quantifier.
+    pub fn is_forall(&self) -> bool {
+        self.kind() == Z3_QUANTIFIER_AST
+            && unsafe { Z3_is_quantifier_forall(self.ctx.z3_ctx, self.z3_ast) }
+    }
+
+    /// Return `true` if this term is an existential quantifier.
+    pub fn is_exists(&self) -> bool {
+        self.kind() == Z3_QUANTIFIER_AST
+            && unsafe { Z3_is_quantifier_exists(self.ctx.z3_ctx, self.z3_ast) }
+    }
+
+    /// Return the names and sorts of the variables bound by a quantifier,
+    /// outermost first.
+    ///
+    /// Panics if this term is not a quantifier.
+    pub fn quantifier_bound_vars(&self) -> Vec<(String, Sort<'ctx>)> {
+        assert_eq!(self.kind(), Z3_QUANTIFIER_AST);
+        let n = unsafe { Z3_get_quantifier_num_bound(self.ctx.z3_ctx, self.z3_ast) };
+        (0..n)
+            .map(|i| unsafe {
+                let name = Z3_get_quantifier_bound_name(self.ctx.z3_ctx, self.z3_ast, i);
+                let sort = Z3_get_quantifier_bound_sort(self.ctx.z3_ctx, self.z3_ast, i);
+                (
+                    symbol_to_string(self.ctx, name),
+                    Sort::from_raw(self.ctx, sort),
+                )
+            })
+            .collect()
+    }
+
+    /// Return the body of a quantifier. Occurrences of the bound variables
+    /// in the body are de Bruijn indexed variables; see
+    /// [`var_index`](#method.var_index).
+    ///
+    /// Panics if this term is not a quantifier.
+    pub fn quantifier_body(&self) -> Ast<'ctx> {
+        assert_eq!(self.kind(), Z3_QUANTIFIER_AST);
+        Ast::new(self.ctx, unsafe {
+            Z3_get_quantifier_body(self.ctx.z3_ctx, self.z3_ast)
+        })
     }
 }
 
@@ -532,6 +1182,12 @@ impl<'ctx> fmt::Display for Ast<'ctx> {
     }
 }
 
+impl<'ctx> fmt::Debug for Ast<'ctx> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        <Self as fmt::Display>::fmt(self, f)
+    }
+}
+
 impl<'ctx> Clone for Ast<'ctx> {
     fn clone(&self) -> Ast<'ctx> {
         debug!("clone ast {:p}", self.z3_ast);
@@ -564,3 +1220,705 @@ impl<'ctx> PartialEq<Ast<'ctx>> for Ast<'ctx> {
 }
 
 impl<'ctx> Eq for Ast<'ctx> {}
+
+// Operator overloading. Bit-vector operands select the (signed) bit-vector
+// variant of an operation, Boolean operands the logical one, and everything
+// else the arithmetic one. Integer literals are converted to the sort of the
+// other operand.
+
+macro_rules! impl_binary_op {
+    ( $Trait:ident, $f:ident, |$a:ident, $b:ident| $body:expr ) => {
+        impl<'a, 'b, 'ctx> ops::$Trait<&'b Ast<'ctx>> for &'a Ast<'ctx> {
+            type Output = Ast<'ctx>;
+
+            fn $f(self, other: &'b Ast<'ctx>) -> Ast<'ctx> {
+                let ($a, $b) = (self, other);
+                $body
+            }
+        }
+
+        impl<'a, 'ctx> ops::$Trait<&'a Ast<'ctx>> for Ast<'ctx> {
+            type Output = Ast<'ctx>;
+
+            fn $f(self, other: &'a Ast<'ctx>) -> Ast<'ctx> {
+                ops::$Trait::$f(&self, other)
+            }
+        }
+
+        impl<'a, 'ctx> ops::$Trait<Ast<'ctx>> for &'a Ast<'ctx> {
+            type Output = Ast<'ctx>;
+
+            fn $f(self, other: Ast<'ctx>) -> Ast<'ctx> {
+                ops::$Trait::$f(self, &other)
+            }
+        }
+
+        impl<'ctx> ops::$Trait<Ast<'ctx>> for Ast<'ctx> {
+            type Output = Ast<'ctx>;
+
+            fn $f(self, other: Ast<'ctx>) -> Ast<'ctx> {
+                ops::$Trait::$f(&self, &other)
+            }
+        }
+
+        impl<'a, 'ctx> ops::$Trait<i64> for &'a Ast<'ctx> {
+            type Output = Ast<'ctx>;
+
+            fn $f(self, other: i64) -> Ast<'ctx> {
+                ops::$Trait::$f(self, &self.sort().from_i64(other))
+            }
+        }
+
+        impl<'ctx> ops::$Trait<i64> for Ast<'ctx> {
+            type Output = Ast<'ctx>;
+
+            fn $f(self, other: i64) -> Ast<'ctx> {
+                ops::$Trait::$f(&self, other)
+            }
+        }
+
+        impl<'a, 'ctx> ops::$Trait<&'a Ast<'ctx>> for i64 {
+            type Output = Ast<'ctx>;
+
+            fn $f(self, other: &'a Ast<'ctx>) -> Ast<'ctx> {
+                ops::$Trait::$f(&other.sort().from_i64(self), other)
+            }
+        }
+
+        impl<'ctx> ops::$Trait<Ast<'ctx>> for i64 {
+            type Output = Ast<'ctx>;
+
+            fn $f(self, other: Ast<'ctx>) -> Ast<'ctx> {
+                ops::$Trait::$f(self, &other)
+            }
+        }
+    };
+}
+
+macro_rules! impl_unary_op {
+    ( $Trait:ident, $f:ident, |$a:ident| $body:expr ) => {
+        impl<'a, 'ctx> ops::$Trait for &'a Ast<'ctx> {
+            type Output = Ast<'ctx>;
+
+            fn $f(self) -> Ast<'ctx> {
+                let $a = self;
+                $body
+            }
+        }
+
+        impl<'ctx> ops::$Trait for Ast<'ctx> {
+            type Output = Ast<'ctx>;
+
+            fn $f(self) -> Ast<'ctx> {
+                ops::$Trait::$f(&self)
+            }
+        }
+    };
+}
+
+impl<'ctx> Ast<'ctx> {
+    fn has_sort_kind(&self, kind: Z3_sort_kind) -> bool {
+        self.sort().kind() == kind
+    }
+}
+
+impl_binary_op!(Add, add, |a, b| if a.has_sort_kind(Z3_BV_SORT) {
+    a.bvadd(b)
+} else {
+    a.add(&[b])
+});
+impl_binary_op!(Sub, sub, |a, b| if a.has_sort_kind(Z3_BV_SORT) {
+    a.bvsub(b)
+} else {
+    a.sub(&[b])
+});
+impl_binary_op!(Mul, mul, |a, b| if a.has_sort_kind(Z3_BV_SORT) {
+    a.bvmul(b)
+} else {
+    a.mul(&[b])
+});
+impl_binary_op!(Div, div, |a, b| if a.has_sort_kind(Z3_BV_SORT) {
+    a.bvsdiv(b)
+} else {
+    a.div(b)
+});
+impl_binary_op!(Rem, rem, |a, b| if a.has_sort_kind(Z3_BV_SORT) {
+    a.bvsrem(b)
+} else {
+    a.rem(b)
+});
+impl_binary_op!(BitAnd, bitand, |a, b| if a.has_sort_kind(Z3_BOOL_SORT) {
+    a.and(&[b])
+} else {
+    a.bvand(b)
+});
+impl_binary_op!(BitOr, bitor, |a, b| if a.has_sort_kind(Z3_BOOL_SORT) {
+    a.or(&[b])
+} else {
+    a.bvor(b)
+});
+impl_binary_op!(BitXor, bitxor, |a, b| if a.has_sort_kind(Z3_BOOL_SORT) {
+    a.xor(b)
+} else {
+    a.bvxor(b)
+});
+impl_binary_op!(Shl, shl, |a, b| a.bvshl(b));
+impl_binary_op!(Shr, shr, |a, b| a.bvashr(b));
+impl_unary_op!(Neg, neg, |a| if a.has_sort_kind(Z3_BV_SORT) {
+    a.bvneg()
+} else {
+    a.minus()
+});
+impl_unary_op!(Not, not, |a| if a.has_sort_kind(Z3_BOOL_SORT) {
+    a.not()
+} else {
+    a.bvnot()
+});
+
+/// Implemented by the typed wrappers around [`Ast`](../struct.Ast.html),
+/// each of which only holds terms of a particular sort.
+pub trait TypedAst<'ctx>: Sized {
+    /// Wrap `ast`, or return `None` if it does not have the right sort.
+    fn from_ast(ast: &Ast<'ctx>) -> Option<Self>;
+
+    /// Return the underlying untyped `Ast`.
+    fn as_ast(&self) -> &Ast<'ctx>;
+}
+
+macro_rules! typed_ast {
+    ( $T:ident, |$sort:ident| $is_sort:expr ) => {
+        impl<'ctx> TypedAst<'ctx> for $T<'ctx> {
+            fn from_ast(ast: &Ast<'ctx>) -> Option<$T<'ctx>> {
+                let $sort = ast.sort();
+                if $is_sort {
+                    Some($T { ast: ast.clone() })
+                } else {
+                    None
+                }
+            }
+
+            fn as_ast(&self) -> &Ast<'ctx> {
+                &self.ast
+            }
+        }
+
+        impl<'ctx> $T<'ctx> {
+            pub fn translate<'dest_ctx>(&self, dest: &'dest_ctx Context) -> $T<'dest_ctx> {
+                $T {
+                    ast: self.ast.translate(dest),
+                }
+            }
+
+            pub fn sort(&self) -> Sort<'ctx> {
+                self.ast.sort()
+            }
+
+            pub fn simplify(&self) -> $T<'ctx> {
+                $T {
+                    ast: self.ast.simplify(),
+                }
+            }
+
+            pub fn _eq(&self, other: &$T<'ctx>) -> Bool<'ctx> {
+                Bool {
+                    ast: self.ast._eq(&other.ast),
+                }
+            }
+
+            typed_varop!(distinct, $T -> Bool);
+        }
+
+        impl<'ctx> From<$T<'ctx>> for Ast<'ctx> {
+            fn from(t: $T<'ctx>) -> Ast<'ctx> {
+                t.ast
+            }
+        }
+
+        impl<'ctx> fmt::Display for $T<'ctx> {
+            fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+                self.ast.fmt(f)
+            }
+        }
+    };
+}
+
+macro_rules! typed_unop {
+    ( $f:ident, $T:ident -> $R:ident ) => {
+        pub fn $f(&self) -> $R<'ctx> {
+            $R { ast: self.ast.$f() }
+        }
+    };
+}
+
+macro_rules! typed_binop {
+    ( $f:ident, $T:ident -> $R:ident ) => {
+        pub fn $f(&self, other: &$T<'ctx>) -> $R<'ctx> {
+            $R {
+                ast: self.ast.$f(&other.ast),
+            }
+        }
+    };
+}
+
+macro_rules! typed_varop {
+    ( $f:ident, $T:ident -> $R:ident ) => {
+        pub fn $f(&self, other: &[&$T<'ctx>]) -> $R<'ctx> {
+            let other: Vec<_> = other.iter().map(|a| &a.ast).collect();
+            $R {
+                ast: self.ast.$f(&other),
+            }
+        }
+    };
+}
+
+/// Boolean term.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Bool<'ctx> {
+    ast: Ast<'ctx>,
+}
+
+/// Integer term.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Int<'ctx> {
+    ast: Ast<'ctx>,
+}
+
+/// Real term.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Real<'ctx> {
+    ast: Ast<'ctx>,
+}
+
+/// Bit-vector term.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct BV<'ctx> {
+    ast: Ast<'ctx>,
+}
+
+/// Array term, mapping values of the domain sort to values of the range sort.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Array<'ctx> {
+    ast: Ast<'ctx>,
+}
+
+/// Set term, i.e. an array whose range is Boolean.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Set<'ctx> {
+    ast: Ast<'ctx>,
+}
+
+/// Term of any sort.
+///
+/// This is the escape hatch for terms whose sort is only known at runtime,
+/// e.g. the result of [`FuncDecl::apply()`](../struct.FuncDecl.html#method.apply)
+/// or [`Array::select()`](struct.Array.html#method.select). Use
+/// [`Dynamic::as_bool()`](#method.as_bool) and friends to get back a typed
+/// term.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Dynamic<'ctx> {
+    ast: Ast<'ctx>,
+}
+
+typed_ast!(Bool, |sort| sort.kind() == Z3_BOOL_SORT);
+typed_ast!(Int, |sort| sort.kind() == Z3_INT_SORT);
+typed_ast!(Real, |sort| sort.kind() == Z3_REAL_SORT);
+typed_ast!(BV, |sort| sort.kind() == Z3_BV_SORT);
+typed_ast!(Array, |sort| sort.kind() == Z3_ARRAY_SORT);
+typed_ast!(Set, |sort| match sort.array_range() {
+    Some(range) => range.kind() == Z3_BOOL_SORT,
+    None => false,
+});
+typed_ast!(Dynamic, |sort| true);
+
+impl<'ctx> Bool<'ctx> {
+    pub fn new_const(ctx: &'ctx Context, name: &str) -> Bool<'ctx> {
+        Bool {
+            ast: ctx.named_bool_const(name),
+        }
+    }
+
+    pub fn fresh_const(ctx: &'ctx Context, prefix: &str) -> Bool<'ctx> {
+        Bool {
+            ast: ctx.fresh_bool_const(prefix),
+        }
+    }
+
+    pub fn from_bool(ctx: &'ctx Context, b: bool) -> Bool<'ctx> {
+        Bool {
+            ast: Ast::from_bool(ctx, b),
+        }
+    }
+
+    pub fn as_bool(&self) -> Option<bool> {
+        self.ast.as_bool()
+    }
+
+    /// Create an if-then-else term, selecting `a` if `self` is true and
+    /// `b` otherwise.
+    pub fn ite<T: TypedAst<'ctx>>(&self, a: &T, b: &T) -> T {
+        T::from_ast(&self.ast.ite(a.as_ast(), b.as_ast())).unwrap()
+    }
+
+    typed_binop!(iff, Bool -> Bool);
+    typed_binop!(implies, Bool -> Bool);
+    typed_binop!(xor, Bool -> Bool);
+    typed_varop!(and, Bool -> Bool);
+    typed_varop!(or, Bool -> Bool);
+    typed_unop!(not, Bool -> Bool);
+}
+
+impl<'ctx> Int<'ctx> {
+    pub fn new_const(ctx: &'ctx Context, name: &str) -> Int<'ctx> {
+        Int {
+            ast: ctx.named_int_const(name),
+        }
+    }
+
+    pub fn fresh_const(ctx: &'ctx Context, prefix: &str) -> Int<'ctx> {
+        Int {
+            ast: ctx.fresh_int_const(prefix),
+        }
+    }
+
+    pub fn from_i64(ctx: &'ctx Context, i: i64) -> Int<'ctx> {
+        Int {
+            ast: Ast::from_i64(ctx, i),
+        }
+    }
+
+    pub fn from_u64(ctx: &'ctx Context, u: u64) -> Int<'ctx> {
+        Int {
+            ast: Ast::from_u64(ctx, u),
+        }
+    }
+
+    pub fn as_i64(&self) -> Option<i64> {
+        self.ast.as_i64()
+    }
+
+    pub fn as_u64(&self) -> Option<u64> {
+        self.ast.as_u64()
+    }
+
+    /// Create a bit vector of width `n` from this integer.
+    pub fn int2bv(&self, n: u64) -> BV<'ctx> {
+        BV {
+            ast: self.ast.int2bv(n),
+        }
+    }
+
+    typed_varop!(add, Int -> Int);
+    typed_varop!(sub, Int -> Int);
+    typed_varop!(mul, Int -> Int);
+    typed_binop!(div, Int -> Int);
+    typed_binop!(rem, Int -> Int);
+    typed_binop!(modulo, Int -> Int);
+    typed_binop!(power, Int -> Int);
+    typed_unop!(minus, Int -> Int);
+    typed_binop!(lt, Int -> Bool);
+    typed_binop!(le, Int -> Bool);
+    typed_binop!(ge, Int -> Bool);
+    typed_binop!(gt, Int -> Bool);
+    typed_unop!(int2real, Int -> Real);
+}
+
+impl<'ctx> Real<'ctx> {
+    pub fn new_const(ctx: &'ctx Context, name: &str) -> Real<'ctx> {
+        Real {
+            ast: ctx.named_real_const(name),
+        }
+    }
+
+    pub fn fresh_const(ctx: &'ctx Context, prefix: &str) -> Real<'ctx> {
+        Real {
+            ast: ctx.fresh_real_const(prefix),
+        }
+    }
+
+    pub fn from_real(ctx: &'ctx Context, num: i32, den: i32) -> Real<'ctx> {
+        Real {
+            ast: Ast::from_real(ctx, num, den),
+        }
+    }
+
+    pub fn as_real(&self) -> Option<(i64, i64)> {
+        self.ast.as_real()
+    }
+
+    typed_varop!(add, Real -> Real);
+    typed_varop!(sub, Real -> Real);
+    typed_varop!(mul, Real -> Real);
+    typed_binop!(div, Real -> Real);
+    typed_binop!(power, Real -> Real);
+    typed_unop!(minus, Real -> Real);
+    typed_binop!(lt, Real -> Bool);
+    typed_binop!(le, Real -> Bool);
+    typed_binop!(ge, Real -> Bool);
+    typed_binop!(gt, Real -> Bool);
+    typed_unop!(real2int, Real -> Int);
+    typed_unop!(is_int, Real -> Bool);
+}
+
+impl<'ctx> BV<'ctx> {
+    pub fn new_const(ctx: &'ctx Context, name: &str, sz: u32) -> BV<'ctx> {
+        BV {
+            ast: ctx.named_bitvector_const(name, sz),
+        }
+    }
+
+    pub fn fresh_const(ctx: &'ctx Context, prefix: &str, sz: u32) -> BV<'ctx> {
+        BV {
+            ast: ctx.fresh_bitvector_const(prefix, sz),
+        }
+    }
+
+    pub fn from_i64(ctx: &'ctx Context, i: i64, sz: u32) -> BV<'ctx> {
+        BV {
+            ast: ctx.bitvector_sort(sz).from_i64(i),
+        }
+    }
+
+    pub fn from_u64(ctx: &'ctx Context, u: u64, sz: u32) -> BV<'ctx> {
+        BV {
+            ast: ctx.bitvector_sort(sz).from_u64(u),
+        }
+    }
+
+    pub fn as_i64(&self) -> Option<i64> {
+        self.ast.as_i64()
+    }
+
+    pub fn as_u64(&self) -> Option<u64> {
+        self.ast.as_u64()
+    }
+
+    /// Return the width of this bit vector.
+    pub fn get_size(&self) -> u32 {
+        self.sort().bv_size().unwrap()
+    }
+
+    /// Create an integer from this bit vector, interpreting it as signed
+    /// or unsigned.
+    pub fn bv2int(&self, signed: bool) -> Int<'ctx> {
+        Int {
+            ast: self.ast.bv2int(signed),
+        }
+    }
+
+    typed_unop!(bvnot, BV -> BV);
+    typed_unop!(bvneg, BV -> BV);
+    typed_unop!(bvredand, BV -> BV);
+    typed_unop!(bvredor, BV -> BV);
+    typed_binop!(bvand, BV -> BV);
+    typed_binop!(bvor, BV -> BV);
+    typed_binop!(bvxor, BV -> BV);
+    typed_binop!(bvnand, BV -> BV);
+    typed_binop!(bvnor, BV -> BV);
+    typed_binop!(bvxnor, BV -> BV);
+    typed_binop!(bvadd, BV -> BV);
+    typed_binop!(bvsub, BV -> BV);
+    typed_binop!(bvmul, BV -> BV);
+    typed_binop!(bvudiv, BV -> BV);
+    typed_binop!(bvsdiv, BV -> BV);
+    typed_binop!(bvurem, BV -> BV);
+    typed_binop!(bvsrem, BV -> BV);
+    typed_binop!(bvsmod, BV -> BV);
+    typed_binop!(bvult, BV -> Bool);
+    typed_binop!(bvslt, BV -> Bool);
+    typed_binop!(bvule, BV -> Bool);
+    typed_binop!(bvsle, BV -> Bool);
+    typed_binop!(bvuge, BV -> Bool);
+    typed_binop!(bvsge, BV -> Bool);
+    typed_binop!(bvugt, BV -> Bool);
+    typed_binop!(bvsgt, BV -> Bool);
+    typed_binop!(concat, BV -> BV);
+    typed_binop!(bvshl, BV -> BV);
+    typed_binop!(bvlshr, BV -> BV);
+    typed_binop!(bvashr, BV -> BV);
+    typed_binop!(bvrotl, BV -> BV);
+    typed_binop!(bvrotr, BV -> BV);
+
+    pub fn bvextract(&self, high: usize, low: usize) -> BV<'ctx> {
+        BV {
+            ast: self.ast.bvextract(high, low),
+        }
+    }
+    pub fn bvrepeat(&self, n: usize) -> BV<'ctx> {
+        BV {
+            ast: self.ast.bvrepeat(n),
+        }
+    }
+    pub fn bvsignextend(&self, n: usize) -> BV<'ctx> {
+        BV {
+            ast: self.ast.bvsignextend(n),
+        }
+    }
+    pub fn bvzeroextend(&self, n: usize) -> BV<'ctx> {
+        BV {
+            ast: self.ast.bvzeroextend(n),
+        }
+    }
+
+    // overflow checks
+    pub fn bvadd_no_overflow(&self, other: &BV<'ctx>, signed: bool) -> Bool<'ctx> {
+        Bool {
+            ast: self.ast.bvadd_no_overflow(&other.ast, signed),
+        }
+    }
+    typed_binop!(bvadd_no_underflow, BV -> Bool);
+    typed_binop!(bvsub_no_overflow, BV -> Bool);
+    pub fn bvsub_no_underflow(&self, other: &BV<'ctx>, signed: bool) -> Bool<'ctx> {
+        Bool {
+            ast: self.ast.bvsub_no_underflow(&other.ast, signed),
+        }
+    }
+    typed_binop!(bvsdiv_no_overflow, BV -> Bool);
+    typed_unop!(bvneg_no_overflow, BV -> Bool);
+    pub fn bvmul_no_overflow(&self, other: &BV<'ctx>, signed: bool) -> Bool<'ctx> {
+        Bool {
+            ast: self.ast.bvmul_no_overflow(&other.ast, signed),
+        }
+    }
+    typed_binop!(bvmul_no_underflow, BV -> Bool);
+}
+
+impl<'ctx> Array<'ctx> {
+    pub fn new_const(
+        ctx: &'ctx Context,
+        name: &str,
+        domain: &Sort<'ctx>,
+        range: &Sort<'ctx>,
+    ) -> Array<'ctx> {
+        Array {
+            ast: ctx.named_const(name, &ctx.array_sort(domain, range)),
+        }
+    }
+
+    pub fn fresh_const(
+        ctx: &'ctx Context,
+        prefix: &str,
+        domain: &Sort<'ctx>,
+        range: &Sort<'ctx>,
+    ) -> Array<'ctx> {
+        Array {
+            ast: ctx.fresh_const(prefix, &ctx.array_sort(domain, range)),
+        }
+    }
+
+    /// Return the value stored at `index`.
+    pub fn select<I: TypedAst<'ctx>>(&self, index: &I) -> Dynamic<'ctx> {
+        Dynamic {
+            ast: self.ast.select(index.as_ast()),
+        }
+    }
+
+    /// Return the array that is equal to `self`, except that it maps
+    /// `index` to `value`.
+    pub fn store<I: TypedAst<'ctx>, V: TypedAst<'ctx>>(&self, index: &I, value: &V) -> Array<'ctx> {
+        Array {
+            ast: self.ast.store(index.as_ast(), value.as_ast()),
+        }
+    }
+}
+
+impl<'ctx> Set<'ctx> {
+    pub fn new_const(ctx: &'ctx Context, name: &str, elt: &Sort<'ctx>) -> Set<'ctx> {
+        Set {
+            ast: ctx.named_const(name, &ctx.set_sort(elt)),
+        }
+    }
+
+    pub fn fresh_const(ctx: &'ctx Context, prefix: &str, elt: &Sort<'ctx>) -> Set<'ctx> {
+        Set {
+            ast: ctx.fresh_const(prefix, &ctx.set_sort(elt)),
+        }
+    }
+
+    /// Create the empty set of elements of sort `elt`.
+    pub fn empty(ctx: &'ctx Context, elt: &Sort<'ctx>) -> Set<'ctx> {
+        Set {
+            ast: Ast::new(ctx, unsafe { Z3_mk_empty_set(ctx.z3_ctx, elt.z3_sort) }),
+        }
+    }
+
+    /// Add `elem` to the set.
+    pub fn set_add<T: TypedAst<'ctx>>(&self, elem: &T) -> Set<'ctx> {
+        Set {
+            ast: self.ast.set_add(elem.as_ast()),
+        }
+    }
+
+    /// Remove `elem` from the set.
+    pub fn set_del<T: TypedAst<'ctx>>(&self, elem: &T) -> Set<'ctx> {
+        Set {
+            ast: self.ast.set_del(elem.as_ast()),
+        }
+    }
+
+    /// Check whether `elem` is a member of the set.
+    pub fn set_member<T: TypedAst<'ctx>>(&self, elem: &T) -> Bool<'ctx> {
+        Bool {
+            ast: elem.as_ast().set_member(&self.ast),
+        }
+    }
+
+    typed_varop!(set_union, Set -> Set);
+    typed_varop!(set_intersect, Set -> Set);
+    typed_binop!(set_subset, Set -> Bool);
+    typed_unop!(set_complement, Set -> Set);
+}
+
+impl<'ctx> Dynamic<'ctx> {
+    pub fn new(ast: Ast<'ctx>) -> Dynamic<'ctx> {
+        Dynamic { ast }
+    }
+
+    /// Return the kind of the sort of this term.
+    pub fn sort_kind(&self) -> Z3_sort_kind {
+        self.sort().kind()
+    }
+
+    pub fn as_bool(&self) -> Option<Bool<'ctx>> {
+        Bool::from_ast(&self.ast)
+    }
+
+    pub fn as_int(&self) -> Option<Int<'ctx>> {
+        Int::from_ast(&self.ast)
+    }
+
+    pub fn as_real(&self) -> Option<Real<'ctx>> {
+        Real::from_ast(&self.ast)
+    }
+
+    pub fn as_bv(&self) -> Option<BV<'ctx>> {
+        BV::from_ast(&self.ast)
+    }
+
+    pub fn as_array(&self) -> Option<Array<'ctx>> {
+        Array::from_ast(&self.ast)
+    }
+
+    pub fn as_set(&self) -> Option<Set<'ctx>> {
+        Set::from_ast(&self.ast)
+    }
+}
+
+impl<'ctx> From<Ast<'ctx>> for Dynamic<'ctx> {
+    fn from(ast: Ast<'ctx>) -> Dynamic<'ctx> {
+        Dynamic { ast }
+    }
+}
+
+macro_rules! into_dynamic {
+    ( $( $T:ident ),* ) => {
+        $(
+            impl<'ctx> From<$T<'ctx>> for Dynamic<'ctx> {
+                fn from(t: $T<'ctx>) -> Dynamic<'ctx> {
+                    Dynamic { ast: t.ast }
+                }
+            }
+        )*
+    };
+}
+
+into_dynamic!(Bool, Int, Real, BV, Array, Set);
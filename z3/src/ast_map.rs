@@ -0,0 +1,103 @@
+use std::ffi::CStr;
+use std::fmt;
+use z3_sys::*;
+use {Ast, AstMap, AstVector, Context};
+
+impl<'ctx> AstMap<'ctx> {
+    /// Create an empty map.
+    ///
+    /// ```
+    /// # use z3::{AstMap, Config, Context};
+    /// # let cfg = Config::new();
+    /// # let ctx = Context::new(&cfg);
+    /// let x = ctx.named_int_const("x");
+    /// let m = AstMap::new(&ctx);
+    /// m.insert(&x, &ctx.from_i64(1));
+    /// m.insert(&x, &ctx.from_i64(2));
+    ///
+    /// assert_eq!(m.len(), 1);
+    /// assert_eq!(m.get(&x), Some(ctx.from_i64(2)));
+    /// m.remove(&x);
+    /// assert_eq!(m.get(&x), None);
+    /// ```
+    pub fn new(ctx: &'ctx Context) -> AstMap<'ctx> {
+        unsafe { AstMap::from_raw(ctx, Z3_mk_ast_map(ctx.z3_ctx)) }
+    }
+
+    pub(crate) unsafe fn from_raw(ctx: &'ctx Context, z3_ast_map: Z3_ast_map) -> AstMap<'ctx> {
+        Z3_ast_map_inc_ref(ctx.z3_ctx, z3_ast_map);
+        AstMap { ctx, z3_ast_map }
+    }
+
+    /// Return the number of entries.
+    pub fn len(&self) -> usize {
+        unsafe { Z3_ast_map_size(self.ctx.z3_ctx, self.z3_ast_map) as usize }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Return `true` if the map has an entry for `key`.
+    pub fn contains_key(&self, key: &Ast<'ctx>) -> bool {
+        unsafe { Z3_ast_map_contains(self.ctx.z3_ctx, self.z3_ast_map, key.z3_ast) }
+    }
+
+    /// Return the value of `key`, or `None` if the map has no entry for it.
+    pub fn get(&self, key: &Ast<'ctx>) -> Option<Ast<'ctx>> {
+        if !self.contains_key(key) {
+            return None;
+        }
+        Some(Ast::new(self.ctx, unsafe {
+            Z3_ast_map_find(self.ctx.z3_ctx, self.z3_ast_map, key.z3_ast)
+        }))
+    }
+
+    /// Map `key` to `value`, replacing the previous value of `key`.
+    pub fn insert(&self, key: &Ast<'ctx>, value: &Ast<'ctx>) {
+        assert_eq!(self.ctx.z3_ctx, key.ctx.z3_ctx);
+        assert_eq!(self.ctx.z3_ctx, value.ctx.z3_ctx);
+        unsafe { Z3_ast_map_insert(self.ctx.z3_ctx, self.z3_ast_map, key.z3_ast, value.z3_ast) };
+    }
+
+    /// Remove the entry for `key`, if any.
+    pub fn remove(&self, key: &Ast<'ctx>) {
+        unsafe { Z3_ast_map_erase(self.ctx.z3_ctx, self.z3_ast_map, key.z3_ast) };
+    }
+
+    /// Remove all entries.
+    pub fn clear(&self) {
+        unsafe { Z3_ast_map_reset(self.ctx.z3_ctx, self.z3_ast_map) };
+    }
+
+    /// Return the keys of the map.
+    pub fn keys(&self) -> Vec<Ast<'ctx>> {
+        unsafe { AstVector::from_raw(self.ctx, Z3_ast_map_keys(self.ctx.z3_ctx, self.z3_ast_map)) }
+            .to_vec()
+    }
+}
+
+impl<'ctx> fmt::Display for AstMap<'ctx> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        let p = unsafe { Z3_ast_map_to_string(self.ctx.z3_ctx, self.z3_ast_map) };
+        if p.is_null() {
+            return Result::Err(fmt::Error);
+        }
+        match unsafe { CStr::from_ptr(p) }.to_str() {
+            Ok(s) => write!(f, "{}", s),
+            Err(_) => Result::Err(fmt::Error),
+        }
+    }
+}
+
+impl<'ctx> fmt::Debug for AstMap<'ctx> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        <Self as fmt::Display>::fmt(self, f)
+    }
+}
+
+impl<'ctx> Drop for AstMap<'ctx> {
+    fn drop(&mut self) {
+        unsafe { Z3_ast_map_dec_ref(self.ctx.z3_ctx, self.z3_ast_map) };
+    }
+}
@@ -0,0 +1,147 @@
+use std::ffi::CStr;
+use std::fmt;
+use std::vec;
+use z3_sys::*;
+use {Ast, AstVector, Context};
+
+impl<'ctx> AstVector<'ctx> {
+    /// Create an empty vector.
+    pub fn new(ctx: &'ctx Context) -> AstVector<'ctx> {
+        unsafe { AstVector::from_raw(ctx, Z3_mk_ast_vector(ctx.z3_ctx)) }
+    }
+
+    pub(crate) unsafe fn from_raw(
+        ctx: &'ctx Context,
+        z3_ast_vector: Z3_ast_vector,
+    ) -> AstVector<'ctx> {
+        Z3_ast_vector_inc_ref(ctx.z3_ctx, z3_ast_vector);
+        AstVector { ctx, z3_ast_vector }
+    }
+
+    /// Return the number of elements.
+    pub fn len(&self) -> usize {
+        unsafe { Z3_ast_vector_size(self.ctx.z3_ctx, self.z3_ast_vector) as usize }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Return the `i`-th element, or `None` if `i` is out of bounds.
+    pub fn get(&self, i: usize) -> Option<Ast<'ctx>> {
+        if i >= self.len() {
+            return None;
+        }
+        Some(Ast::new(self.ctx, unsafe {
+            Z3_ast_vector_get(self.ctx.z3_ctx, self.z3_ast_vector, i as u32)
+        }))
+    }
+
+    /// Replace the `i`-th element by `ast`.
+    ///
+    /// Panics if `i` is out of bounds.
+    pub fn set(&self, i: usize, ast: &Ast<'ctx>) {
+        assert!(i < self.len(), "index out of bounds");
+        assert_eq!(self.ctx.z3_ctx, ast.ctx.z3_ctx);
+        unsafe { Z3_ast_vector_set(self.ctx.z3_ctx, self.z3_ast_vector, i as u32, ast.z3_ast) };
+    }
+
+    /// Append `ast` to the end of the vector.
+    ///
+    /// ```
+    /// # use z3::{AstVector, Config, Context};
+    /// # let cfg = Config::new();
+    /// # let ctx = Context::new(&cfg);
+    /// let v = AstVector::new(&ctx);
+    /// v.push(&ctx.from_i64(1));
+    /// v.push(&ctx.from_i64(2));
+    /// v.set(0, &ctx.from_i64(3));
+    ///
+    /// let values: Vec<_> = v.iter().map(|a| a.as_i64().unwrap()).collect();
+    /// assert_eq!(values, [3, 2]);
+    /// ```
+    pub fn push(&self, ast: &Ast<'ctx>) {
+        assert_eq!(self.ctx.z3_ctx, ast.ctx.z3_ctx);
+        unsafe { Z3_ast_vector_push(self.ctx.z3_ctx, self.z3_ast_vector, ast.z3_ast) };
+    }
+
+    /// Resize the vector to `len` elements, like
+    /// [`Vec::resize()`](https://doc.rust-lang.org/std/vec/struct.Vec.html#method.resize):
+    /// extra elements are dropped, and new elements are set to `value`.
+    pub fn resize(&self, len: usize, value: &Ast<'ctx>) {
+        let old_len = self.len();
+        unsafe { Z3_ast_vector_resize(self.ctx.z3_ctx, self.z3_ast_vector, len as u32) };
+        for i in old_len..len {
+            self.set(i, value);
+        }
+    }
+
+    /// Copy the vector and its elements into the context `dest`.
+    pub fn translate<'dest_ctx>(&self, dest: &'dest_ctx Context) -> AstVector<'dest_ctx> {
+        unsafe {
+            AstVector::from_raw(
+                dest,
+                Z3_ast_vector_translate(self.ctx.z3_ctx, self.z3_ast_vector, dest.z3_ctx),
+            )
+        }
+    }
+
+    /// Iterate over the elements.
+    pub fn iter(&self) -> vec::IntoIter<Ast<'ctx>> {
+        self.to_vec().into_iter()
+    }
+
+    /// Copy the elements into a `Vec`.
+    pub fn to_vec(&self) -> Vec<Ast<'ctx>> {
+        (0..self.len()).map(|i| self.get(i).unwrap()).collect()
+    }
+}
+
+impl<'ctx> IntoIterator for AstVector<'ctx> {
+    type Item = Ast<'ctx>;
+    type IntoIter = vec::IntoIter<Ast<'ctx>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'ctx> IntoIterator for &AstVector<'ctx> {
+    type Item = Ast<'ctx>;
+    type IntoIter = vec::IntoIter<Ast<'ctx>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'ctx> From<AstVector<'ctx>> for Vec<Ast<'ctx>> {
+    fn from(v: AstVector<'ctx>) -> Vec<Ast<'ctx>> {
+        v.to_vec()
+    }
+}
+
+impl<'ctx> fmt::Display for AstVector<'ctx> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        let p = unsafe { Z3_ast_vector_to_string(self.ctx.z3_ctx, self.z3_ast_vector) };
+        if p.is_null() {
+            return Result::Err(fmt::Error);
+        }
+        match unsafe { CStr::from_ptr(p) }.to_str() {
+            Ok(s) => write!(f, "{}", s),
+            Err(_) => Result::Err(fmt::Error),
+        }
+    }
+}
+
+impl<'ctx> fmt::Debug for AstVector<'ctx> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        <Self as fmt::Display>::fmt(self, f)
+    }
+}
+
+impl<'ctx> Drop for AstVector<'ctx> {
+    fn drop(&mut self) {
+        unsafe { Z3_ast_vector_dec_ref(self.ctx.z3_ctx, self.z3_ast_vector) };
+    }
+}
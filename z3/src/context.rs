@@ -1,22 +1,215 @@
+use error::{c_path, c_string};
+use std::path::Path;
 use z3_sys::*;
 use Config;
 use Context;
+use ContextHandle;
 use FuncDecl;
 use Sort;
 use Symbol;
-use {Ast, Params, Pattern};
+use {Ast, AstVector, Params, Pattern, Z3Error};
 
 impl Context {
+    /// Create a new context.
+    ///
+    /// No error handler is installed, so failing API calls do not abort the
+    /// process. Instead, the `try_` variants of fallible operations report
+    /// a [`Z3Error`](struct.Z3Error.html), and the plain variants panic.
     pub fn new(cfg: &Config) -> Context {
         Context {
             z3_ctx: unsafe {
                 let p = Z3_mk_context_rc(cfg.z3_cfg);
                 debug!("new context {:p}", p);
+                Z3_set_error_handler(p, None);
                 p
             },
         }
     }
 
+    /// Return the error of the last API call made on this context, if any.
+    pub(crate) fn check_error(&self) -> Result<(), Z3Error> {
+        match unsafe { Z3_get_error_code(self.z3_ctx) } {
+            Z3_OK => Ok(()),
+            code => Err(Z3Error::new(self, code)),
+        }
+    }
+
+    /// Clear the error of a previous API call.
+    ///
+    /// Most API calls do this themselves, but some, such as the SMT-LIB2
+    /// parsers, do not, so checking their error after a failed call would
+    /// report the stale error.
+    pub(crate) fn reset_error(&self) {
+        unsafe { Z3_set_error(self.z3_ctx, Z3_OK) };
+    }
+
+    /// Interrupt the computation currently running in this context, e.g.
+    /// a [`Solver::check()`](struct.Solver.html#method.check). The
+    /// interrupted computation returns
+    /// [`SatResult::Unknown`](enum.SatResult.html#variant.Unknown), and
+    /// [`Solver::get_reason_unknown()`](struct.Solver.html#method.get_reason_unknown)
+    /// reports `"canceled"` or `"interrupted"`, depending on the Z3 version.
+    ///
+    /// Use [`Context::handle()`](#method.handle) to interrupt a
+    /// computation from another thread.
+    pub fn interrupt(&self) {
+        self.handle().interrupt();
+    }
+
+    /// Return a handle that can be sent to other threads to interrupt
+    /// computations running in this context.
+    ///
+    /// # Examples
+    /// ```
+    /// # use z3::{Config, Context, SatResult, Solver};
+    /// # use std::sync::atomic::{AtomicBool, Ordering};
+    /// # use std::{thread, time::Duration};
+    /// let cfg = Config::new();
+    /// let ctx = Context::new(&cfg);
+    /// let solver = Solver::new(&ctx);
+    /// // An integer solution of x^3 + y^3 = z^3 does not exist, and Z3
+    /// // cannot prove it.
+    /// let (x, y, z) = (
+    ///     ctx.named_int_const("x"),
+    ///     ctx.named_int_const("y"),
+    ///     ctx.named_int_const("z"),
+    /// );
+    /// let x3 = x.mul(&[&x, &x]);
+    /// let y3 = y.mul(&[&y, &y]);
+    /// solver.assert(&x3.add(&[&y3])._eq(&z.mul(&[&z, &z])));
+    /// solver.assert(&x.gt(&ctx.from_i64(0)));
+    /// solver.assert(&y.gt(&ctx.from_i64(0)));
+    ///
+    /// let handle = ctx.handle();
+    /// let done = AtomicBool::new(false);
+    /// thread::scope(|s| {
+    ///     // An interrupt that arrives before the check starts is lost, so
+    ///     // keep interrupting until the check returns.
+    ///     s.spawn(|| {
+    ///         while !done.load(Ordering::SeqCst) {
+    ///             thread::sleep(Duration::from_millis(10));
+    ///             handle.interrupt();
+    ///         }
+    ///     });
+    ///     let result = solver.check();
+    ///     done.store(true, Ordering::SeqCst);
+    ///     assert_eq!(result, SatResult::Unknown);
+    /// });
+    /// ```
+    pub fn handle(&self) -> ContextHandle<'_> {
+        ContextHandle { ctx: self }
+    }
+
+    /// Parse an SMT-LIB2 string and return the asserted formulas.
+    ///
+    /// Commands other than declarations, definitions and assertions (such
+    /// as `check-sat`) are ignored. The sorts and function declarations in
+    /// `sorts` and `decls` can be referred to by name in the input without
+    /// being declared there; this binds parsed names to existing
+    /// declarations.
+    ///
+    /// # Examples
+    /// ```
+    /// # use z3::{Config, Context, SatResult, Solver};
+    /// # let cfg = Config::new();
+    /// # let ctx = Context::new(&cfg);
+    /// let x = ctx.named_int_const("x");
+    /// let formulas = ctx
+    ///     .parse_smtlib2_string("(assert (> x 2))", &[], &[&x.decl()])
+    ///     .unwrap();
+    ///
+    /// let solver = Solver::new(&ctx);
+    /// solver.assert(&formulas.get(0).unwrap());
+    /// solver.assert(&x.lt(&ctx.from_i64(4)));
+    /// assert_eq!(solver.check(), SatResult::Sat);
+    /// assert_eq!(3, solver.get_model().eval(&x).unwrap().as_i64().unwrap());
+    /// ```
+    pub fn parse_smtlib2_string<'ctx>(
+        &'ctx self,
+        source: &str,
+        sorts: &[&Sort<'ctx>],
+        decls: &[&FuncDecl<'ctx>],
+    ) -> Result<AstVector<'ctx>, Z3Error> {
+        let source = c_string(source, Z3_PARSER_ERROR)?;
+        let (sort_names, sorts, decl_names, decls) = self.smtlib2_names(sorts, decls);
+        self.reset_error();
+        let v = unsafe {
+            Z3_parse_smtlib2_string(
+                self.z3_ctx,
+                source.as_ptr(),
+                sorts.len() as u32,
+                sort_names.as_ptr(),
+                sorts.as_ptr(),
+                decls.len() as u32,
+                decl_names.as_ptr(),
+                decls.as_ptr(),
+            )
+        };
+        self.check_error()?;
+        Ok(unsafe { AstVector::from_raw(self, v) })
+    }
+
+    /// Parse an SMT-LIB2 file and return the asserted formulas.
+    ///
+    /// # See also:
+    ///
+    /// - [`Context::parse_smtlib2_string()`](#method.parse_smtlib2_string)
+    pub fn parse_smtlib2_file<'ctx, P: AsRef<Path>>(
+        &'ctx self,
+        path: P,
+        sorts: &[&Sort<'ctx>],
+        decls: &[&FuncDecl<'ctx>],
+    ) -> Result<AstVector<'ctx>, Z3Error> {
+        let path = c_path(path.as_ref())?;
+        let (sort_names, sorts, decl_names, decls) = self.smtlib2_names(sorts, decls);
+        self.reset_error();
+        let v = unsafe {
+            Z3_parse_smtlib2_file(
+                self.z3_ctx,
+                path.as_ptr(),
+                sorts.len() as u32,
+                sort_names.as_ptr(),
+                sorts.as_ptr(),
+                decls.len() as u32,
+                decl_names.as_ptr(),
+                decls.as_ptr(),
+            )
+        };
+        self.check_error()?;
+        Ok(unsafe { AstVector::from_raw(self, v) })
+    }
+
+    /// Return the names and raw handles of the sorts and declarations passed
+    /// to the SMT-LIB2 parser.
+    fn smtlib2_names<'ctx>(
+        &'ctx self,
+        sorts: &[&Sort<'ctx>],
+        decls: &[&FuncDecl<'ctx>],
+    ) -> (
+        Vec<Z3_symbol>,
+        Vec<Z3_sort>,
+        Vec<Z3_symbol>,
+        Vec<Z3_func_decl>,
+    ) {
+        assert!(sorts.iter().all(|s| s.ctx.z3_ctx == self.z3_ctx));
+        assert!(decls.iter().all(|d| d.ctx.z3_ctx == self.z3_ctx));
+
+        let (sort_names, sorts) = sorts
+            .iter()
+            .map(|s| unsafe { (Z3_get_sort_name(self.z3_ctx, s.z3_sort), s.z3_sort) })
+            .unzip();
+        let (decl_names, decls) = decls
+            .iter()
+            .map(|d| unsafe {
+                (
+                    Z3_get_decl_name(self.z3_ctx, d.z3_func_decl),
+                    d.z3_func_decl,
+                )
+            })
+            .unzip();
+        (sort_names, sorts, decl_names, decls)
+    }
+
     // Helpers for common constructions
 
     pub fn bool_sort(&self) -> Sort {
@@ -35,6 +228,18 @@ impl Context {
         Sort::bitvector(self, sz)
     }
 
+    pub fn float_sort(&self, ebits: u32, sbits: u32) -> Sort<'_> {
+        Sort::float(self, ebits, sbits)
+    }
+
+    pub fn rounding_mode_sort(&self) -> Sort<'_> {
+        Sort::rounding_mode(self)
+    }
+
+    pub fn string_sort(&self) -> Sort<'_> {
+        Sort::string(self)
+    }
+
     pub fn array_sort<'ctx>(&'ctx self, domain: &Sort<'ctx>, range: &Sort<'ctx>) -> Sort<'ctx> {
         Sort::array(self, domain, range)
     }
@@ -54,7 +259,7 @@ impl Context {
     ///
     /// # Examples
     /// ```
-    /// # use z3::{Config, Context, Solver};
+    /// # use z3::{Config, Context, SatResult, Solver};
     /// # let cfg = Config::new();
     /// # let ctx = Context::new(&cfg);
     /// # let solver = Solver::new(&ctx);
@@ -71,7 +276,7 @@ impl Context {
     /// let red_tester = &color_testers[0];
     /// let eq = red_tester.apply(&[&red_const]);
     ///
-    /// assert!(solver.check());
+    /// assert_eq!(solver.check(), SatResult::Sat);
     /// let model = solver.get_model();
     ///
     /// assert!(model.eval(&eq).unwrap().as_bool().unwrap());
@@ -197,7 +402,7 @@ impl Context {
     ///
     /// # Examples
     /// ```
-    /// # use z3::{Config, Context, Solver};
+    /// # use z3::{Config, Context, SatResult, Solver};
     /// # let cfg = Config::new();
     /// # let ctx = Context::new(&cfg);
     /// # let solver = Solver::new(&ctx);
@@ -207,7 +412,7 @@ impl Context {
     /// let f_x = f.apply(&[&x]);
     /// solver.assert(&ctx.forall_const(&[&x], &x._eq(&f_x)));
     ///
-    /// assert!(solver.check());
+    /// assert_eq!(solver.check(), SatResult::Sat);
     /// let model = solver.get_model();
     ///
     /// let f_f_3 = f.apply(&[&f.apply(&[&ctx.from_u64(3)])]);
@@ -240,6 +445,28 @@ impl Context {
     }
 }
 
+impl<'ctx> ContextHandle<'ctx> {
+    /// Interrupt the computation currently running in the context.
+    ///
+    /// # See also:
+    ///
+    /// - [`Context::interrupt()`](struct.Context.html#method.interrupt)
+    pub fn interrupt(&self) {
+        unsafe { Z3_interrupt(self.ctx.z3_ctx) };
+    }
+}
+
+// `Z3_interrupt` is the only operation of a handle, and it may be called
+// from any thread.
+unsafe impl<'ctx> Send for ContextHandle<'ctx> {}
+unsafe impl<'ctx> Sync for ContextHandle<'ctx> {}
+
+// Z3 contexts may be used from any thread, as long as they are not used by
+// several threads at once. Objects created in a context hold a shared
+// reference to it, and since `Context` is not `Sync` they cannot leave the
+// thread that owns the context.
+unsafe impl Send for Context {}
+
 impl Drop for Context {
     fn drop(&mut self) {
         unsafe { Z3_del_context(self.z3_ctx) };
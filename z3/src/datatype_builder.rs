@@ -1,6 +1,22 @@
 use std::{convert::TryInto, ptr::null_mut};
 use z3_sys::*;
-use {Context, Datatype, DatatypeBuilder, DatatypeVariant, FuncDecl, Sort};
+use {
+    Context, Datatype, DatatypeAccessor, DatatypeBuilder, DatatypeVariant, FuncDecl, Sort, Z3Error,
+};
+
+/// Sort of a field, with references to datatypes kept by name or index
+/// until all datatypes are created.
+pub(crate) enum FieldSort<'ctx> {
+    Sort(Sort<'ctx>),
+    Datatype(String),
+    DatatypeIndex(usize),
+}
+
+impl<'a, 'ctx> From<&'a Sort<'ctx>> for DatatypeAccessor<'a, 'ctx> {
+    fn from(sort: &'a Sort<'ctx>) -> DatatypeAccessor<'a, 'ctx> {
+        DatatypeAccessor::Sort(sort)
+    }
+}
 
 impl<'ctx> DatatypeBuilder<'ctx> {
     pub fn new(ctx: &'ctx Context) -> Self {
@@ -10,106 +26,268 @@ impl<'ctx> DatatypeBuilder<'ctx> {
         }
     }
 
-    pub fn variant(mut self, name: &str, fields: &[(&str, &Sort)]) -> Self {
-        let recognizer_name_sym = self.ctx.str_sym(&format!("is-{}", name));
-        let name_sym = self.ctx.str_sym(name);
+    pub fn variant(self, name: &str, fields: &[(&str, &Sort<'ctx>)]) -> Self {
+        let fields: Vec<_> = fields
+            .iter()
+            .map(|&(name, sort)| (name, DatatypeAccessor::Sort(sort)))
+            .collect();
+        self.variant_with(name, &fields)
+    }
 
-        assert!(fields
+    /// Add a variant whose fields may refer to the datatype being built, or
+    /// to the other datatypes passed to
+    /// [`DatatypeBuilder::create_datatypes()`](#method.create_datatypes).
+    ///
+    /// ```
+    /// # use z3::{Config, Context, DatatypeAccessor, DatatypeBuilder, SatResult, Solver};
+    /// # let cfg = Config::new();
+    /// # let ctx = Context::new(&cfg);
+    /// // Like Rust's `enum IntList { Nil, Cons(i64, Box<IntList>) }`
+    /// let list = DatatypeBuilder::new(&ctx)
+    ///     .variant("Nil", &[])
+    ///     .variant_with(
+    ///         "Cons",
+    ///         &[
+    ///             ("head", DatatypeAccessor::Sort(&ctx.int_sort())),
+    ///             ("tail", DatatypeAccessor::Datatype("IntList")),
+    ///         ],
+    ///     )
+    ///     .finish("IntList");
+    ///
+    /// let nil = list.variants[0].constructor.apply(&[]);
+    /// let one = list.variants[1].constructor.apply(&[&ctx.from_i64(1), &nil]);
+    /// let tail = list.variants[1].accessors[1].apply(&[&one]);
+    /// assert_eq!(tail.simplify(), nil);
+    /// ```
+    pub fn variant_with(
+        mut self,
+        name: &str,
+        fields: &[(&str, DatatypeAccessor<'_, 'ctx>)],
+    ) -> Self {
+        let fields = fields
             .iter()
-            .all(|(name, sort)| sort.ctx.z3_ctx == self.ctx.z3_ctx));
+            .map(|(field_name, accessor)| {
+                let sort = match *accessor {
+                    DatatypeAccessor::Sort(sort) => {
+                        assert_eq!(sort.ctx.z3_ctx, self.ctx.z3_ctx);
+                        FieldSort::Sort(unsafe { Sort::from_raw(self.ctx, sort.z3_sort) })
+                    }
+                    DatatypeAccessor::Datatype(name) => FieldSort::Datatype(name.to_owned()),
+                    DatatypeAccessor::DatatypeIndex(i) => FieldSort::DatatypeIndex(i),
+                };
+                ((*field_name).to_owned(), sort)
+            })
+            .collect();
+        self.variants.push((name.to_owned(), fields));
+        self
+    }
 
-        let mut field_names = Vec::with_capacity(fields.len());
-        let mut field_sorts = Vec::with_capacity(fields.len());
+    pub fn finish(self, name: &str) -> Datatype<'ctx> {
+        self.try_finish(name).unwrap()
+    }
 
-        for (name, sort) in fields {
-            field_names.push(self.ctx.str_sym(name).z3_sym);
-            field_sorts.push(sort.z3_sort);
-        }
+    /// Like [`DatatypeBuilder::finish()`](#method.finish), but reports an
+    /// error instead of panicking if Z3 rejects the datatype.
+    pub fn try_finish(self, name: &str) -> Result<Datatype<'ctx>, Z3Error> {
+        Ok(DatatypeBuilder::try_create_datatypes(vec![(name, self)])?
+            .pop()
+            .unwrap())
+    }
 
-        // This is unused.
-        // Z3 expects sort_refs in Z3_mk_constructor to valid, so create it here.
-        let mut sort_refs = Vec::new();
-        sort_refs.resize(fields.len(), 0);
-
-        let constructor = unsafe {
-            Z3_mk_constructor(
-                self.ctx.z3_ctx,
-                name_sym.z3_sym,
-                recognizer_name_sym.z3_sym,
-                fields.len().try_into().unwrap(),
-                field_names.as_ptr(),
-                field_sorts.as_ptr(),
-                sort_refs.as_mut_ptr(),
-            )
-        };
-
-        self.variants.push((fields.len(), constructor));
-        self
+    /// Create several datatypes at once, which may refer to each other by
+    /// name or by their index in `datatypes`.
+    ///
+    /// Panics if `datatypes` is empty, or a field refers to a datatype
+    /// that is not in `datatypes`.
+    ///
+    /// ```
+    /// # use z3::{Config, Context, DatatypeAccessor, DatatypeBuilder};
+    /// # let cfg = Config::new();
+    /// # let ctx = Context::new(&cfg);
+    /// // A tree has an integer value and a forest of children, and a
+    /// // forest is a list of trees.
+    /// let tree = DatatypeBuilder::new(&ctx).variant_with(
+    ///     "node",
+    ///     &[
+    ///         ("value", DatatypeAccessor::Sort(&ctx.int_sort())),
+    ///         ("children", DatatypeAccessor::Datatype("Forest")),
+    ///     ],
+    /// );
+    /// let forest = DatatypeBuilder::new(&ctx)
+    ///     .variant("nil", &[])
+    ///     .variant_with(
+    ///         "cons",
+    ///         &[
+    ///             ("first", DatatypeAccessor::DatatypeIndex(0)),
+    ///             ("rest", DatatypeAccessor::DatatypeIndex(1)),
+    ///         ],
+    ///     );
+    ///
+    /// let datatypes = DatatypeBuilder::create_datatypes(vec![("Tree", tree), ("Forest", forest)]);
+    /// assert_eq!(datatypes[0].sort.to_string(), "Tree");
+    /// assert_eq!(datatypes[1].variants[1].accessors[0].range(), datatypes[0].sort);
+    /// ```
+    pub fn create_datatypes(datatypes: Vec<(&str, DatatypeBuilder<'ctx>)>) -> Vec<Datatype<'ctx>> {
+        DatatypeBuilder::try_create_datatypes(datatypes).unwrap()
     }
 
-    pub fn finish(self, name: &str) -> Datatype<'ctx> {
-        let mut constructors: Vec<_> = self.variants.iter().map(|i| i.1).collect();
-        let name_sym = self.ctx.str_sym(name);
-
-        let sort = unsafe {
-            let s = Z3_mk_datatype(
-                self.ctx.z3_ctx,
-                name_sym.z3_sym,
-                constructors.len().try_into().unwrap(),
-                constructors.as_mut_ptr(),
+    /// Like [`DatatypeBuilder::create_datatypes()`](#method.create_datatypes),
+    /// but reports an error instead of panicking if Z3 rejects the
+    /// datatypes, e.g. because one of them has no finite values.
+    ///
+    /// ```
+    /// # use z3::{Config, Context, DatatypeAccessor, DatatypeBuilder};
+    /// # let cfg = Config::new();
+    /// # let ctx = Context::new(&cfg);
+    /// // Every stream contains another stream, so there are no streams.
+    /// let stream = DatatypeBuilder::new(&ctx).variant_with(
+    ///     "cons",
+    ///     &[
+    ///         ("head", DatatypeAccessor::Sort(&ctx.int_sort())),
+    ///         ("tail", DatatypeAccessor::Datatype("Stream")),
+    ///     ],
+    /// );
+    /// assert!(DatatypeBuilder::try_create_datatypes(vec![("Stream", stream)]).is_err());
+    /// ```
+    pub fn try_create_datatypes(
+        datatypes: Vec<(&str, DatatypeBuilder<'ctx>)>,
+    ) -> Result<Vec<Datatype<'ctx>>, Z3Error> {
+        assert!(!datatypes.is_empty());
+        let ctx = datatypes[0].1.ctx;
+        assert!(datatypes.iter().all(|(_, b)| b.ctx.z3_ctx == ctx.z3_ctx));
+
+        let names: Vec<&str> = datatypes.iter().map(|(name, _)| *name).collect();
+        let sort_names: Vec<_> = names.iter().map(|name| ctx.str_sym(name).z3_sym).collect();
+
+        let constructors: Vec<Vec<Z3_constructor>> = datatypes
+            .iter()
+            .map(|(_, builder)| {
+                builder
+                    .variants
+                    .iter()
+                    .map(|(name, fields)| {
+                        let recognizer_name_sym = ctx.str_sym(&format!("is-{}", name));
+                        let name_sym = ctx.str_sym(name);
+
+                        let mut field_names = Vec::with_capacity(fields.len());
+                        let mut field_sorts = Vec::with_capacity(fields.len());
+                        let mut sort_refs = Vec::with_capacity(fields.len());
+                        for (field_name, sort) in fields {
+                            field_names.push(ctx.str_sym(field_name).z3_sym);
+                            // Z3 reads `sort_refs` only for fields without a sort.
+                            let (z3_sort, sort_ref) = match sort {
+                                FieldSort::Sort(sort) => (sort.z3_sort, 0),
+                                FieldSort::Datatype(target) => {
+                                    let i = names.iter().position(|n| n == target);
+                                    (null_mut(), i.expect("unknown datatype name"))
+                                }
+                                FieldSort::DatatypeIndex(i) => {
+                                    assert!(*i < names.len(), "datatype index out of bounds");
+                                    (null_mut(), *i)
+                                }
+                            };
+                            field_sorts.push(z3_sort);
+                            sort_refs.push(sort_ref.try_into().unwrap());
+                        }
+
+                        unsafe {
+                            Z3_mk_constructor(
+                                ctx.z3_ctx,
+                                name_sym.z3_sym,
+                                recognizer_name_sym.z3_sym,
+                                fields.len().try_into().unwrap(),
+                                field_names.as_ptr(),
+                                field_sorts.as_ptr(),
+                                sort_refs.as_mut_ptr(),
+                            )
+                        }
+                    })
+                    .collect()
+            })
+            .collect();
+
+        let mut constructor_lists: Vec<Z3_constructor_list> = constructors
+            .iter()
+            .map(|cs| unsafe {
+                Z3_mk_constructor_list(ctx.z3_ctx, cs.len().try_into().unwrap(), cs.as_ptr())
+            })
+            .collect();
+        let mut sorts: Vec<Z3_sort> = vec![null_mut(); datatypes.len()];
+
+        unsafe {
+            Z3_mk_datatypes(
+                ctx.z3_ctx,
+                datatypes.len().try_into().unwrap(),
+                sort_names.as_ptr(),
+                sorts.as_mut_ptr(),
+                constructor_lists.as_mut_ptr(),
             );
-            Z3_inc_ref(self.ctx.z3_ctx, Z3_sort_to_ast(self.ctx.z3_ctx, s));
-            Sort {
-                ctx: self.ctx,
-                z3_sort: s,
-            }
-        };
-
-        // create independent fields
-        let (ctx, variants) = (self.ctx, self.variants);
-
-        let variants = variants
-            .into_iter()
-            .map(|(num_fields, constructor)| {
-                let mut constructor_func: Z3_func_decl = null_mut();
-                let mut tester: Z3_func_decl = null_mut();
-                let mut accessors: Vec<Z3_func_decl> = Vec::new();
-                accessors.resize(num_fields, null_mut());
-
-                unsafe {
-                    // fill fields
-                    Z3_query_constructor(
-                        ctx.z3_ctx,
-                        constructor,
-                        num_fields.try_into().unwrap(),
-                        &mut constructor_func,
-                        &mut tester,
-                        accessors.as_mut_ptr(),
-                    );
-
-                    Z3_del_constructor(ctx.z3_ctx, constructor);
-
-                    // convert to Rust types
-                    let constructor = FuncDecl::from_raw(ctx, constructor_func);
-                    let tester = FuncDecl::from_raw(ctx, tester);
-                    let accessors = accessors
-                        .iter()
-                        .map(|f| FuncDecl::from_raw(ctx, *f))
-                        .collect();
-
-                    DatatypeVariant {
-                        constructor,
-                        tester,
-                        accessors,
-                    }
+        }
+        if let Err(e) = ctx.check_error() {
+            del_constructors(ctx, constructor_lists, constructors);
+            return Err(e);
+        }
+
+        let result = datatypes
+            .iter()
+            .zip(sorts)
+            .zip(&constructors)
+            .map(|(((_, builder), sort), constructors)| {
+                let variants = builder
+                    .variants
+                    .iter()
+                    .zip(constructors)
+                    .map(|((_, fields), constructor)| {
+                        let mut constructor_func: Z3_func_decl = null_mut();
+                        let mut tester: Z3_func_decl = null_mut();
+                        let mut accessors: Vec<Z3_func_decl> = vec![null_mut(); fields.len()];
+
+                        unsafe {
+                            // fill fields
+                            Z3_query_constructor(
+                                ctx.z3_ctx,
+                                *constructor,
+                                fields.len().try_into().unwrap(),
+                                &mut constructor_func,
+                                &mut tester,
+                                accessors.as_mut_ptr(),
+                            );
+
+                            // convert to Rust types
+                            DatatypeVariant {
+                                constructor: FuncDecl::from_raw(ctx, constructor_func),
+                                tester: FuncDecl::from_raw(ctx, tester),
+                                accessors: accessors
+                                    .iter()
+                                    .map(|f| FuncDecl::from_raw(ctx, *f))
+                                    .collect(),
+                            }
+                        }
+                    })
+                    .collect();
+
+                Datatype {
+                    ctx,
+                    sort: unsafe { Sort::from_raw(ctx, sort) },
+                    variants,
                 }
             })
             .collect();
 
-        Datatype {
-            ctx: self.ctx,
-            sort,
-            variants,
-        }
+        del_constructors(ctx, constructor_lists, constructors);
+        Ok(result)
+    }
+}
+
+fn del_constructors(
+    ctx: &Context,
+    constructor_lists: Vec<Z3_constructor_list>,
+    constructors: Vec<Vec<Z3_constructor>>,
+) {
+    for list in constructor_lists {
+        unsafe { Z3_del_constructor_list(ctx.z3_ctx, list) };
+    }
+    for constructor in constructors.into_iter().flatten() {
+        unsafe { Z3_del_constructor(ctx.z3_ctx, constructor) };
     }
 }
@@ -0,0 +1,62 @@
+use std::ffi::{CStr, CString};
+use std::fmt;
+use std::path::Path;
+use z3_sys::*;
+use Context;
+use Z3Error;
+
+impl Z3Error {
+    pub(crate) fn new(ctx: &Context, code: Z3_error_code) -> Z3Error {
+        let msg = unsafe {
+            let p = Z3_get_error_msg(ctx.z3_ctx, code);
+            if p.is_null() {
+                String::new()
+            } else {
+                CStr::from_ptr(p).to_string_lossy().into_owned()
+            }
+        };
+        Z3Error { code, msg }
+    }
+
+    /// Create an error that is detected by the bindings rather than
+    /// reported by Z3.
+    pub(crate) fn with_message(code: Z3_error_code, msg: String) -> Z3Error {
+        Z3Error { code, msg }
+    }
+
+    /// The error code reported by Z3.
+    pub fn code(&self) -> Z3_error_code {
+        self.code
+    }
+
+    /// The human-readable description of the error reported by Z3.
+    pub fn message(&self) -> &str {
+        &self.msg
+    }
+}
+
+/// Convert `s` for Z3, reporting an error with `code` if it contains a NUL
+/// byte.
+pub(crate) fn c_string(s: &str, code: Z3_error_code) -> Result<CString, Z3Error> {
+    CString::new(s).map_err(|e| Z3Error::with_message(code, e.to_string()))
+}
+
+/// Convert `path` for Z3, reporting an error if it is not valid UTF-8 or
+/// contains a NUL byte.
+pub(crate) fn c_path(path: &Path) -> Result<CString, Z3Error> {
+    let error = || {
+        Z3Error::with_message(
+            Z3_FILE_ACCESS_ERROR,
+            format!("invalid path: {}", path.display()),
+        )
+    };
+    CString::new(path.to_str().ok_or_else(error)?).map_err(|_| error())
+}
+
+impl fmt::Display for Z3Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        write!(f, "{:?}: {}", self.code, self.msg)
+    }
+}
+
+impl std::error::Error for Z3Error {}
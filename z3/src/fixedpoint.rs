@@ -0,0 +1,331 @@
+use error::{c_path, c_string};
+use std::ffi::CStr;
+use std::fmt;
+use std::path::Path;
+use std::ptr::null_mut;
+use z3_sys::*;
+use Context;
+use Fixedpoint;
+use SatResult;
+use {Ast, AstVector, FuncDecl, ParamDescrs, Params, Stats, Symbol, Z3Error};
+
+impl<'ctx> Fixedpoint<'ctx> {
+    /// Create a new fixedpoint context for solving Datalog queries and
+    /// constrained Horn clauses.
+    pub fn new(ctx: &'ctx Context) -> Fixedpoint<'ctx> {
+        Fixedpoint {
+            ctx,
+            z3_fixedpoint: unsafe {
+                let fp = Z3_mk_fixedpoint(ctx.z3_ctx);
+                Z3_fixedpoint_inc_ref(ctx.z3_ctx, fp);
+                fp
+            },
+        }
+    }
+
+    /// Register `relation` as a predicate whose interpretation is the
+    /// least fixed point of the rules, rather than an arbitrary function.
+    ///
+    /// Relations must be registered before rules mentioning them are
+    /// added.
+    pub fn register_relation(&self, relation: &FuncDecl<'ctx>) {
+        self.try_register_relation(relation).unwrap()
+    }
+
+    /// Like [`Fixedpoint::register_relation()`](#method.register_relation),
+    /// but reports an error instead of panicking if `relation` cannot be
+    /// registered.
+    pub fn try_register_relation(&self, relation: &FuncDecl<'ctx>) -> Result<(), Z3Error> {
+        self.ctx.reset_error();
+        unsafe {
+            Z3_fixedpoint_register_relation(
+                self.ctx.z3_ctx,
+                self.z3_fixedpoint,
+                relation.z3_func_decl,
+            )
+        };
+        self.ctx.check_error()
+    }
+
+    /// Add the Horn clause `rule`, e.g. `(forall (x y) (=> (edge x y) (path x y)))`,
+    /// optionally under a `name`.
+    ///
+    /// Variables of the rule must be bound by a universal quantifier.
+    ///
+    /// ```
+    /// # use z3::{Config, Context, Fixedpoint, SatResult};
+    /// # let cfg = Config::new();
+    /// # let ctx = Context::new(&cfg);
+    /// let int = ctx.int_sort();
+    /// let bool = ctx.bool_sort();
+    /// let inv = ctx.func_decl(ctx.str_sym("inv"), &[&int], &bool);
+    /// let x = ctx.named_const("x", &int);
+    ///
+    /// // inv(0), inv(x) => inv(x + 2)
+    /// let fp = Fixedpoint::new(&ctx);
+    /// fp.register_relation(&inv);
+    /// fp.add_rule(&inv.apply(&[&ctx.from_i64(0)]), None);
+    /// let step = inv.apply(&[&x]).implies(&inv.apply(&[&x.add(&[&ctx.from_i64(2)])]));
+    /// fp.add_rule(&ctx.forall_const(&[&x], &step), Some("step"));
+    ///
+    /// // inv(x) never holds for an odd x.
+    /// let odd = inv.apply(&[&x]).and(&[&x.modulo(&ctx.from_i64(2))._eq(&ctx.from_i64(1))]);
+    /// assert_eq!(fp.query(&ctx.exists_const(&[&x], &odd)), SatResult::Unsat);
+    /// ```
+    pub fn add_rule(&self, rule: &Ast<'ctx>, name: Option<&str>) {
+        self.try_add_rule(rule, name).unwrap()
+    }
+
+    /// Like [`Fixedpoint::add_rule()`](#method.add_rule), but reports an
+    /// error instead of panicking if `rule` is not a Horn clause.
+    pub fn try_add_rule(&self, rule: &Ast<'ctx>, name: Option<&str>) -> Result<(), Z3Error> {
+        let name = name.map(|name| Symbol::from_string(self.ctx, name));
+        let z3_sym = name.as_ref().map_or(null_mut(), |name| name.z3_sym);
+        unsafe { Z3_fixedpoint_add_rule(self.ctx.z3_ctx, self.z3_fixedpoint, rule.z3_ast, z3_sym) };
+        self.ctx.check_error()
+    }
+
+    /// Add the fact `relation(args)` to a relation over finite domains,
+    /// such as bit-vectors, where each argument is given by its numeric
+    /// value.
+    ///
+    /// This is a faster way to add ground rules for the `datalog` engine.
+    pub fn add_fact(&self, relation: &FuncDecl<'ctx>, args: &[u32]) {
+        self.try_add_fact(relation, args).unwrap()
+    }
+
+    /// Like [`Fixedpoint::add_fact()`](#method.add_fact), but reports an
+    /// error instead of panicking if `args` do not fit `relation`.
+    pub fn try_add_fact(&self, relation: &FuncDecl<'ctx>, args: &[u32]) -> Result<(), Z3Error> {
+        let mut args = args.to_vec();
+        unsafe {
+            Z3_fixedpoint_add_fact(
+                self.ctx.z3_ctx,
+                self.z3_fixedpoint,
+                relation.z3_func_decl,
+                args.len() as u32,
+                args.as_mut_ptr(),
+            )
+        };
+        self.ctx.check_error()
+    }
+
+    /// Check whether `query` is derivable from the rules.
+    ///
+    /// The result is [`SatResult::Sat`](enum.SatResult.html#variant.Sat)
+    /// if it is, and [`SatResult::Unsat`](enum.SatResult.html#variant.Unsat)
+    /// if it is not. In both cases
+    /// [`Fixedpoint::get_answer()`](#method.get_answer) explains why.
+    pub fn query(&self, query: &Ast<'ctx>) -> SatResult {
+        self.try_query(query).unwrap()
+    }
+
+    /// Like [`Fixedpoint::query()`](#method.query), but reports an error
+    /// instead of panicking if the query or the rules are not supported by
+    /// the engine.
+    pub fn try_query(&self, query: &Ast<'ctx>) -> Result<SatResult, Z3Error> {
+        let result =
+            unsafe { Z3_fixedpoint_query(self.ctx.z3_ctx, self.z3_fixedpoint, query.z3_ast) };
+        self.ctx.check_error()?;
+        Ok(match result {
+            Z3_L_FALSE => SatResult::Unsat,
+            Z3_L_UNDEF => SatResult::Unknown,
+            Z3_L_TRUE => SatResult::Sat,
+        })
+    }
+
+    /// Check whether any tuple of any of `relations` is derivable from the
+    /// rules.
+    ///
+    /// # See also:
+    ///
+    /// - [`Fixedpoint::query()`](#method.query)
+    pub fn query_relations(&self, relations: &[&FuncDecl<'ctx>]) -> SatResult {
+        self.try_query_relations(relations).unwrap()
+    }
+
+    /// Like [`Fixedpoint::query_relations()`](#method.query_relations), but
+    /// reports an error instead of panicking if the query or the rules are
+    /// not supported by the engine.
+    pub fn try_query_relations(&self, relations: &[&FuncDecl<'ctx>]) -> Result<SatResult, Z3Error> {
+        let r: Vec<Z3_func_decl> = relations.iter().map(|r| r.z3_func_decl).collect();
+        let result = unsafe {
+            Z3_fixedpoint_query_relations(
+                self.ctx.z3_ctx,
+                self.z3_fixedpoint,
+                r.len() as u32,
+                r.as_ptr(),
+            )
+        };
+        self.ctx.check_error()?;
+        Ok(match result {
+            Z3_L_FALSE => SatResult::Unsat,
+            Z3_L_UNDEF => SatResult::Unknown,
+            Z3_L_TRUE => SatResult::Sat,
+        })
+    }
+
+    /// Retrieve the answer to the last query.
+    ///
+    /// For a derivable query this describes the derivation, or the set of
+    /// derivable tuples for the `datalog` engine; otherwise it is an
+    /// inductive invariant that excludes the query. Returns `None` if no
+    /// answer is available.
+    pub fn get_answer(&self) -> Option<Ast<'ctx>> {
+        let ast = unsafe { Z3_fixedpoint_get_answer(self.ctx.z3_ctx, self.z3_fixedpoint) };
+        if ast.is_null() {
+            None
+        } else {
+            Some(Ast::new(self.ctx, ast))
+        }
+    }
+
+    /// Retrieve a string that describes the last status returned by
+    /// [`Fixedpoint::query()`](#method.query).
+    ///
+    /// Use this method when [`Fixedpoint::query()`](#method.query) returns
+    /// [`SatResult::Unknown`](enum.SatResult.html#variant.Unknown).
+    pub fn get_reason_unknown(&self) -> Option<String> {
+        let p = unsafe { Z3_fixedpoint_get_reason_unknown(self.ctx.z3_ctx, self.z3_fixedpoint) };
+        if p.is_null() {
+            return None;
+        }
+        unsafe { CStr::from_ptr(p) }
+            .to_str()
+            .ok()
+            .map(|s| s.to_string())
+    }
+
+    /// Return the rules, including facts, of this context.
+    pub fn get_rules(&self) -> Vec<Ast<'ctx>> {
+        unsafe {
+            AstVector::from_raw(
+                self.ctx,
+                Z3_fixedpoint_get_rules(self.ctx.z3_ctx, self.z3_fixedpoint),
+            )
+        }
+        .to_vec()
+    }
+
+    /// Load the relations and rules of the SMT-LIB2 script `source`, which
+    /// may use the `declare-rel`, `declare-var`, `rule` and `query`
+    /// commands, into this context. Each `query` takes the name of a
+    /// relation.
+    ///
+    /// Returns the queries of the script, or an error if it cannot be
+    /// parsed. Z3 prints the location and cause of a parse error to
+    /// standard error rather than including them in the error.
+    ///
+    /// ```
+    /// # use z3::{Config, Context, Fixedpoint, SatResult};
+    /// # let cfg = Config::new();
+    /// # let ctx = Context::new(&cfg);
+    /// let fp = Fixedpoint::new(&ctx);
+    /// let queries = fp
+    ///     .load_string(
+    ///         "(declare-rel even (Int))
+    ///          (declare-rel four ())
+    ///          (declare-var n Int)
+    ///          (rule (even 0))
+    ///          (rule (=> (even n) (even (+ n 2))))
+    ///          (rule (=> (even 4) four))
+    ///          (query four)",
+    ///     )
+    ///     .unwrap();
+    /// assert_eq!(fp.query(&queries[0]), SatResult::Sat);
+    ///
+    /// assert!(fp.load_string("(rule (odd 1))").is_err());
+    /// ```
+    pub fn load_string(&self, source: &str) -> Result<Vec<Ast<'ctx>>, Z3Error> {
+        let source = c_string(source, Z3_PARSER_ERROR)?;
+        self.ctx.reset_error();
+        let queries = unsafe {
+            Z3_fixedpoint_from_string(self.ctx.z3_ctx, self.z3_fixedpoint, source.as_ptr())
+        };
+        self.check_load_error()?;
+        Ok(unsafe { AstVector::from_raw(self.ctx, queries) }.to_vec())
+    }
+
+    /// Load the relations and rules of an SMT-LIB2 file into this context,
+    /// like [`Fixedpoint::load_string()`](#method.load_string).
+    pub fn load_file<P: AsRef<Path>>(&self, path: P) -> Result<Vec<Ast<'ctx>>, Z3Error> {
+        let path = c_path(path.as_ref())?;
+        self.ctx.reset_error();
+        let queries =
+            unsafe { Z3_fixedpoint_from_file(self.ctx.z3_ctx, self.z3_fixedpoint, path.as_ptr()) };
+        self.check_load_error()?;
+        Ok(unsafe { AstVector::from_raw(self.ctx, queries) }.to_vec())
+    }
+
+    /// Report the error of loading a script. Z3 reports parse errors only
+    /// as a generic "parser error", so point to where the details are.
+    fn check_load_error(&self) -> Result<(), Z3Error> {
+        self.ctx.check_error().map_err(|e| {
+            if e.code() == Z3_PARSER_ERROR {
+                Z3Error::with_message(
+                    e.code(),
+                    format!("{} (details were printed to standard error)", e.message()),
+                )
+            } else {
+                e
+            }
+        })
+    }
+
+    /// Set the parameters of the fixedpoint context, e.g. the `engine`.
+    ///
+    /// Panics if a parameter is unknown or has the wrong type; see
+    /// [`Fixedpoint::get_param_descrs()`](#method.get_param_descrs).
+    pub fn set_params(&self, params: &Params<'ctx>) {
+        self.try_set_params(params).unwrap()
+    }
+
+    /// Set the parameters of the fixedpoint context, reporting an error if
+    /// a parameter is unknown or has the wrong type.
+    pub fn try_set_params(&self, params: &Params<'ctx>) -> Result<(), Z3Error> {
+        unsafe { Z3_fixedpoint_set_params(self.ctx.z3_ctx, self.z3_fixedpoint, params.z3_params) };
+        self.ctx.check_error()
+    }
+
+    /// Return statistics about the last
+    /// [`Fixedpoint::query()`](#method.query).
+    pub fn get_statistics(&self) -> Stats<'ctx> {
+        Stats::with_fixedpoint(self)
+    }
+
+    pub fn get_help(&self) -> String {
+        unsafe {
+            let s = Z3_fixedpoint_get_help(self.ctx.z3_ctx, self.z3_fixedpoint);
+            CStr::from_ptr(s).to_str().unwrap().to_string()
+        }
+    }
+
+    pub fn get_param_descrs(&self) -> ParamDescrs<'ctx> {
+        unsafe {
+            ParamDescrs::new(
+                self.ctx,
+                Z3_fixedpoint_get_param_descrs(self.ctx.z3_ctx, self.z3_fixedpoint),
+            )
+        }
+    }
+}
+
+impl<'ctx> fmt::Display for Fixedpoint<'ctx> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        let p =
+            unsafe { Z3_fixedpoint_to_string(self.ctx.z3_ctx, self.z3_fixedpoint, 0, null_mut()) };
+        if p.is_null() {
+            return Result::Err(fmt::Error);
+        }
+        match unsafe { CStr::from_ptr(p) }.to_str() {
+            Ok(s) => write!(f, "{}", s),
+            Err(_) => Result::Err(fmt::Error),
+        }
+    }
+}
+
+impl<'ctx> Drop for Fixedpoint<'ctx> {
+    fn drop(&mut self) {
+        unsafe { Z3_fixedpoint_dec_ref(self.ctx.z3_ctx, self.z3_fixedpoint) };
+    }
+}
@@ -1,4 +1,5 @@
 use std::convert::TryInto;
+use symbol::symbol_to_string;
 use z3_sys::*;
 use {Ast, Context, FuncDecl, Sort, Symbol};
 
@@ -49,6 +50,44 @@ impl<'ctx> FuncDecl<'ctx> {
             )
         })
     }
+
+    /// Return the kind of this declaration, e.g. `Z3_OP_ADD` for `+` or
+    /// `Z3_OP_UNINTERPRETED` for user-declared functions and constants.
+    pub fn kind(&self) -> Z3_decl_kind {
+        unsafe { Z3_get_decl_kind(self.ctx.z3_ctx, self.z3_func_decl) }
+    }
+
+    /// Return the name of this declaration.
+    pub fn name(&self) -> String {
+        unsafe {
+            symbol_to_string(
+                self.ctx,
+                Z3_get_decl_name(self.ctx.z3_ctx, self.z3_func_decl),
+            )
+        }
+    }
+
+    /// Return the number of arguments of this declaration.
+    pub fn arity(&self) -> usize {
+        unsafe { Z3_get_arity(self.ctx.z3_ctx, self.z3_func_decl) as usize }
+    }
+
+    /// Return the sorts of the arguments of this declaration.
+    pub fn domain(&self) -> Vec<Sort<'ctx>> {
+        (0..self.arity())
+            .map(|i| unsafe {
+                Sort::from_raw(
+                    self.ctx,
+                    Z3_get_domain(self.ctx.z3_ctx, self.z3_func_decl, i.try_into().unwrap()),
+                )
+            })
+            .collect()
+    }
+
+    /// Return the sort of the result of this declaration.
+    pub fn range(&self) -> Sort<'ctx> {
+        unsafe { Sort::from_raw(self.ctx, Z3_get_range(self.ctx.z3_ctx, self.z3_func_decl)) }
+    }
 }
 
 impl<'ctx> std::fmt::Display for FuncDecl<'ctx> {
@@ -0,0 +1,51 @@
+use std::fmt;
+use z3_sys::*;
+use {Ast, Context, FuncEntry};
+
+impl<'ctx> FuncEntry<'ctx> {
+    pub(crate) unsafe fn from_raw(
+        ctx: &'ctx Context,
+        z3_func_entry: Z3_func_entry,
+    ) -> FuncEntry<'ctx> {
+        Z3_func_entry_inc_ref(ctx.z3_ctx, z3_func_entry);
+        FuncEntry { ctx, z3_func_entry }
+    }
+
+    /// Return the arguments of the entry.
+    pub fn args(&self) -> Vec<Ast<'ctx>> {
+        let n = unsafe { Z3_func_entry_get_num_args(self.ctx.z3_ctx, self.z3_func_entry) };
+        (0..n)
+            .map(|i| {
+                Ast::new(self.ctx, unsafe {
+                    Z3_func_entry_get_arg(self.ctx.z3_ctx, self.z3_func_entry, i)
+                })
+            })
+            .collect()
+    }
+
+    /// Return the value of the function for the arguments of the entry.
+    pub fn value(&self) -> Ast<'ctx> {
+        Ast::new(self.ctx, unsafe {
+            Z3_func_entry_get_value(self.ctx.z3_ctx, self.z3_func_entry)
+        })
+    }
+}
+
+impl<'ctx> fmt::Display for FuncEntry<'ctx> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        let args: Vec<String> = self.args().iter().map(|a| a.to_string()).collect();
+        write!(f, "{} -> {}", args.join(", "), self.value())
+    }
+}
+
+impl<'ctx> fmt::Debug for FuncEntry<'ctx> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        <Self as fmt::Display>::fmt(self, f)
+    }
+}
+
+impl<'ctx> Drop for FuncEntry<'ctx> {
+    fn drop(&mut self) {
+        unsafe { Z3_func_entry_dec_ref(self.ctx.z3_ctx, self.z3_func_entry) };
+    }
+}
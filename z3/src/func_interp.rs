@@ -0,0 +1,94 @@
+use std::fmt;
+use z3_sys::*;
+use {Ast, AstVector, Context, FuncEntry, FuncInterp};
+
+impl<'ctx> FuncInterp<'ctx> {
+    pub(crate) unsafe fn from_raw(
+        ctx: &'ctx Context,
+        z3_func_interp: Z3_func_interp,
+    ) -> FuncInterp<'ctx> {
+        Z3_func_interp_inc_ref(ctx.z3_ctx, z3_func_interp);
+        FuncInterp {
+            ctx,
+            z3_func_interp,
+        }
+    }
+
+    /// Return the number of arguments of the function.
+    pub fn arity(&self) -> usize {
+        unsafe { Z3_func_interp_get_arity(self.ctx.z3_ctx, self.z3_func_interp) as usize }
+    }
+
+    /// Return the number of entries, i.e. of arguments for which the value
+    /// is given explicitly.
+    pub fn num_entries(&self) -> usize {
+        unsafe { Z3_func_interp_get_num_entries(self.ctx.z3_ctx, self.z3_func_interp) as usize }
+    }
+
+    /// Return the entries of the interpretation.
+    pub fn entries(&self) -> Vec<FuncEntry<'ctx>> {
+        (0..self.num_entries())
+            .map(|i| unsafe {
+                FuncEntry::from_raw(
+                    self.ctx,
+                    Z3_func_interp_get_entry(self.ctx.z3_ctx, self.z3_func_interp, i as u32),
+                )
+            })
+            .collect()
+    }
+
+    /// Return the value of the function for all arguments that are not
+    /// covered by an entry.
+    pub fn else_value(&self) -> Ast<'ctx> {
+        Ast::new(self.ctx, unsafe {
+            Z3_func_interp_get_else(self.ctx.z3_ctx, self.z3_func_interp)
+        })
+    }
+
+    /// Set the value of the function for all arguments that are not
+    /// covered by an entry.
+    pub fn set_else(&self, value: &Ast<'ctx>) {
+        unsafe { Z3_func_interp_set_else(self.ctx.z3_ctx, self.z3_func_interp, value.z3_ast) };
+    }
+
+    /// Add an entry mapping `args` to `value`.
+    ///
+    /// Panics if the number of arguments is not the arity of the function.
+    pub fn add_entry(&self, args: &[&Ast<'ctx>], value: &Ast<'ctx>) {
+        assert_eq!(args.len(), self.arity());
+        let v = AstVector::new(self.ctx);
+        for arg in args {
+            v.push(arg);
+        }
+        unsafe {
+            Z3_func_interp_add_entry(
+                self.ctx.z3_ctx,
+                self.z3_func_interp,
+                v.z3_ast_vector,
+                value.z3_ast,
+            )
+        };
+    }
+}
+
+impl<'ctx> fmt::Display for FuncInterp<'ctx> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        write!(f, "[")?;
+        for entry in self.entries() {
+            write!(f, "{}, ", entry)?;
+        }
+        write!(f, "else -> {}]", self.else_value())
+    }
+}
+
+impl<'ctx> fmt::Debug for FuncInterp<'ctx> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        <Self as fmt::Display>::fmt(self, f)
+    }
+}
+
+impl<'ctx> Drop for FuncInterp<'ctx> {
+    fn drop(&mut self) {
+        unsafe { Z3_func_interp_dec_ref(self.ctx.z3_ctx, self.z3_func_interp) };
+    }
+}
@@ -0,0 +1,125 @@
+use std::ffi::CStr;
+use std::fmt;
+use z3_sys::*;
+use {Ast, Context, Goal, Z3Error};
+
+impl<'ctx> Goal<'ctx> {
+    /// Create a new, empty goal.
+    ///
+    /// - `models`: whether model generation is enabled for the goal.
+    /// - `unsat_cores`: whether unsat core generation is enabled.
+    /// - `proofs`: whether proof generation is enabled. This requires
+    ///   [proof generation](struct.Config.html#method.set_proof_generation)
+    ///   to be enabled for the context.
+    pub fn new(ctx: &'ctx Context, models: bool, unsat_cores: bool, proofs: bool) -> Goal<'ctx> {
+        unsafe { Goal::from_raw(ctx, Z3_mk_goal(ctx.z3_ctx, models, unsat_cores, proofs)) }
+    }
+
+    pub(crate) unsafe fn from_raw(ctx: &'ctx Context, z3_goal: Z3_goal) -> Goal<'ctx> {
+        Z3_goal_inc_ref(ctx.z3_ctx, z3_goal);
+        Goal { ctx, z3_goal }
+    }
+
+    pub fn translate<'dest_ctx>(&self, dest: &'dest_ctx Context) -> Goal<'dest_ctx> {
+        unsafe {
+            Goal::from_raw(
+                dest,
+                Z3_goal_translate(self.ctx.z3_ctx, self.z3_goal, dest.z3_ctx),
+            )
+        }
+    }
+
+    /// Add a new formula to the goal. Conjunctions are split into their
+    /// conjuncts.
+    ///
+    /// Panics if `ast` is not a Boolean expression.
+    ///
+    /// # See also:
+    ///
+    /// - [`Goal::try_assert()`](#method.try_assert)
+    pub fn assert(&self, ast: &Ast<'ctx>) {
+        self.try_assert(ast).unwrap()
+    }
+
+    /// Add a new formula to the goal, reporting an error if `ast` is not
+    /// a Boolean expression.
+    pub fn try_assert(&self, ast: &Ast<'ctx>) -> Result<(), Z3Error> {
+        unsafe { Z3_goal_assert(self.ctx.z3_ctx, self.z3_goal, ast.z3_ast) };
+        self.ctx.check_error()
+    }
+
+    /// Return whether the goal contains the formula `false`.
+    pub fn is_inconsistent(&self) -> bool {
+        unsafe { Z3_goal_inconsistent(self.ctx.z3_ctx, self.z3_goal) }
+    }
+
+    /// Return whether the goal is empty and precise or the product of an
+    /// under-approximation, i.e. whether it is known to be satisfiable.
+    pub fn is_decided_sat(&self) -> bool {
+        unsafe { Z3_goal_is_decided_sat(self.ctx.z3_ctx, self.z3_goal) }
+    }
+
+    /// Return whether the goal contains `false` and is precise or the
+    /// product of an over-approximation, i.e. whether it is known to be
+    /// unsatisfiable.
+    pub fn is_decided_unsat(&self) -> bool {
+        unsafe { Z3_goal_is_decided_unsat(self.ctx.z3_ctx, self.z3_goal) }
+    }
+
+    /// Return the precision of the goal: whether it is precise, or an
+    /// under- and/or over-approximation of the original goal.
+    pub fn precision(&self) -> Z3_goal_prec {
+        unsafe { Z3_goal_precision(self.ctx.z3_ctx, self.z3_goal) }
+    }
+
+    /// Return the depth of the goal, i.e. the number of transformations
+    /// that were applied to produce it.
+    pub fn depth(&self) -> u32 {
+        unsafe { Z3_goal_depth(self.ctx.z3_ctx, self.z3_goal) }
+    }
+
+    /// Return the number of formulas in the goal.
+    pub fn size(&self) -> u32 {
+        unsafe { Z3_goal_size(self.ctx.z3_ctx, self.z3_goal) }
+    }
+
+    /// Return the number of formulas, subformulas and terms in the goal.
+    pub fn num_exprs(&self) -> u32 {
+        unsafe { Z3_goal_num_exprs(self.ctx.z3_ctx, self.z3_goal) }
+    }
+
+    /// Return the formulas in the goal.
+    pub fn formulas(&self) -> Vec<Ast<'ctx>> {
+        (0..self.size())
+            .map(|i| {
+                Ast::new(self.ctx, unsafe {
+                    Z3_goal_formula(self.ctx.z3_ctx, self.z3_goal, i)
+                })
+            })
+            .collect()
+    }
+
+    /// Remove all formulas from the goal.
+    pub fn reset(&self) {
+        unsafe { Z3_goal_reset(self.ctx.z3_ctx, self.z3_goal) };
+    }
+}
+
+impl<'ctx> fmt::Display for Goal<'ctx> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        let p = unsafe { Z3_goal_to_string(self.ctx.z3_ctx, self.z3_goal) };
+        if p.is_null() {
+            return Result::Err(fmt::Error);
+        }
+        match unsafe { CStr::from_ptr(p) }.to_str() {
+            Ok(s) => write!(f, "{}", s),
+            Err(_) => Result::Err(fmt::Error),
+        }
+    }
+}
+
+impl<'ctx> Drop for Goal<'ctx> {
+    fn drop(&mut self) {
+        unsafe { Z3_goal_dec_ref(self.ctx.z3_ctx, self.z3_goal) };
+    }
+}
@@ -6,23 +6,41 @@ extern crate log;
 
 extern crate z3_sys;
 
+#[cfg(feature = "arbitrary-size-numeral")]
+extern crate num_bigint;
+#[cfg(feature = "arbitrary-size-numeral")]
+extern crate num_rational;
+
+use datatype_builder::FieldSort;
 use std::ffi::CString;
 use z3_sys::*;
 
-mod ast;
+mod apply_result;
+pub mod ast;
+mod ast_map;
+mod ast_vector;
 mod config;
 mod context;
 mod datatype_builder;
+mod error;
+mod fixedpoint;
 mod func_decl;
+mod func_entry;
+mod func_interp;
+mod goal;
 mod model;
+#[cfg(feature = "arbitrary-size-numeral")]
+mod numeral;
 mod optimize;
 mod param_descrs;
 mod params;
 mod pattern;
+mod probe;
 mod solver;
 mod sort;
 mod stats;
 mod symbol;
+mod tactic;
 
 /// Configuration used to initialize logical contexts.
 pub struct Config {
@@ -30,10 +48,59 @@ pub struct Config {
 }
 
 /// Manager of all other Z3 objects, global configuration options, etc.
+///
+/// A context can be moved to another thread, but all objects created in it
+/// borrow the context and therefore stay on the thread that owns it. They
+/// are not `Send`:
+///
+/// ```compile_fail
+/// # use z3::{Config, Context};
+/// fn assert_send<T: Send>(_: &T) {}
+/// let cfg = Config::new();
+/// let ctx = Context::new(&cfg);
+/// let x = ctx.named_int_const("x");
+/// assert_send(&x);
+/// ```
+///
+/// ```compile_fail
+/// # use z3::{Config, Context, Solver};
+/// fn assert_send<T: Send>(_: &T) {}
+/// let cfg = Config::new();
+/// let ctx = Context::new(&cfg);
+/// let solver = Solver::new(&ctx);
+/// assert_send(&solver);
+/// ```
+///
+/// Use [`Ast::translate()`](struct.Ast.html#method.translate) or
+/// [`Solver::translate()`](struct.Solver.html#method.translate) to copy
+/// objects into a context owned by another thread.
 pub struct Context {
     z3_ctx: Z3_context,
 }
 
+/// Handle that can be used to interrupt a computation of a
+/// [`Context`](struct.Context.html) from another thread.
+///
+/// # See also:
+///
+/// - [`Context::handle()`](struct.Context.html#method.handle)
+#[derive(Clone, Copy)]
+pub struct ContextHandle<'ctx> {
+    ctx: &'ctx Context,
+}
+
+/// Error reported by Z3 when an API call fails, e.g. because of a sort
+/// mismatch or an invalid argument.
+///
+/// Contexts are created without an error handler, so instead of aborting
+/// the process Z3 records the error code, which the `try_` variants of
+/// fallible operations turn into a `Z3Error`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Z3Error {
+    code: Z3_error_code,
+    msg: String,
+}
+
 /// Symbols are used to name several term and type constructors.
 ///
 /// # Creation:
@@ -62,12 +129,55 @@ pub struct Ast<'ctx> {
     z3_ast: Z3_ast,
 }
 
+/// Vector of [`Ast`](struct.Ast.html) nodes, as returned by the SMT-LIB2
+/// parser, or built to pass terms to Z3.
+pub struct AstVector<'ctx> {
+    ctx: &'ctx Context,
+    z3_ast_vector: Z3_ast_vector,
+}
+
+/// Map from [`Ast`](struct.Ast.html) nodes to `Ast` nodes.
+pub struct AstMap<'ctx> {
+    ctx: &'ctx Context,
+    z3_ast_map: Z3_ast_map,
+}
+
+/// Result of a satisfiability query.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum SatResult {
+    /// There is no model satisfying the assertions.
+    Unsat,
+    /// The solver was unable to decide whether the assertions are
+    /// satisfiable, e.g. because of a timeout or an incomplete theory.
+    Unknown,
+    /// There is a model satisfying the assertions.
+    Sat,
+}
+
 /// (Incremental) solver, possibly specialized by a particular tactic or logic.
 pub struct Solver<'ctx> {
     ctx: &'ctx Context,
     z3_slv: Z3_solver,
 }
 
+/// Iterator over the models of a [`Solver`](struct.Solver.html) that differ
+/// in the values of some terms, created by
+/// [`Solver::iter_models()`](struct.Solver.html#method.iter_models).
+pub struct SolverModels<'s, 'ctx: 's> {
+    solver: &'s Solver<'ctx>,
+    scopes: u32,
+    vars: Vec<Ast<'ctx>>,
+    limit: Option<usize>,
+    result: Option<SatResult>,
+}
+
+/// Configuration of one of the solvers of
+/// [`Solver::check_portfolio()`](struct.Solver.html#method.check_portfolio).
+///
+/// It is called with the context of the solver and empty parameters, and
+/// sets the parameters the solver should use.
+pub type PortfolioConfig = dyn Fn(&Context, &Params) + Sync;
+
 /// Model for the constraints inserted into the logical context.
 pub struct Model<'ctx> {
     ctx: &'ctx Context,
@@ -80,11 +190,42 @@ pub struct Optimize<'ctx> {
     z3_opt: Z3_optimize,
 }
 
+/// Context for solving Datalog queries and constrained Horn clauses.
+pub struct Fixedpoint<'ctx> {
+    ctx: &'ctx Context,
+    z3_fixedpoint: Z3_fixedpoint,
+}
+
+/// Handle to an objective of an [`Optimize`](struct.Optimize.html) context,
+/// returned when the objective is added and used to query its bounds.
+///
+/// The handle is only valid for the `Optimize` context that created it.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Objective {
+    z3_opt: Z3_optimize,
+    index: u32,
+}
+
 pub struct FuncDecl<'ctx> {
     ctx: &'ctx Context,
     z3_func_decl: Z3_func_decl,
 }
 
+/// Interpretation of a function in a [`Model`](struct.Model.html): a finite
+/// list of [`FuncEntry`](struct.FuncEntry.html)s mapping arguments to
+/// values, and a default value for all other arguments.
+pub struct FuncInterp<'ctx> {
+    ctx: &'ctx Context,
+    z3_func_interp: Z3_func_interp,
+}
+
+/// Entry of a [`FuncInterp`](struct.FuncInterp.html): the value of a
+/// function for particular arguments.
+pub struct FuncEntry<'ctx> {
+    ctx: &'ctx Context,
+    z3_func_entry: Z3_func_entry,
+}
+
 pub struct Pattern<'ctx> {
     ctx: &'ctx Context,
     z3_pattern: Z3_pattern,
@@ -95,6 +236,28 @@ pub struct Params<'ctx> {
     z3_params: Z3_params,
 }
 
+/// Builder for the common parameters of a [`Solver`](struct.Solver.html).
+///
+/// Example:
+/// ```
+/// # use z3::{Config, Context, SatResult, Solver, SolverParams};
+/// # let cfg = Config::new();
+/// # let ctx = Context::new(&cfg);
+/// let solver = Solver::new(&ctx);
+/// SolverParams::new(&ctx)
+///     .timeout_msec(10_000)
+///     .random_seed(42)
+///     .unsat_core(true)
+///     .apply(&solver);
+///
+/// let x = ctx.named_int_const("x");
+/// solver.assert(&x.gt(&ctx.from_i64(2)));
+/// assert_eq!(solver.check(), SatResult::Sat);
+/// ```
+pub struct SolverParams<'ctx> {
+    params: Params<'ctx>,
+}
+
 pub struct ParamDescrs<'ctx> {
     ctx: &'ctx Context,
     z3_param_descrs: Z3_param_descrs,
@@ -105,11 +268,51 @@ pub struct Stats<'ctx> {
     z3_stats: Z3_stats,
 }
 
+/// Value of an entry of [`Stats`](struct.Stats.html).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum StatsValue {
+    UInt(u32),
+    Double(f64),
+}
+
+/// Set of formulas that can be solved and/or transformed using
+/// [`Tactic`](struct.Tactic.html)s.
+pub struct Goal<'ctx> {
+    ctx: &'ctx Context,
+    z3_goal: Z3_goal,
+}
+
+/// Basic building block for creating custom solvers for specific problem
+/// domains.
+///
+/// Z3 provides a variety of tactics, which can be queried using
+/// [`Tactic::list_all()`](struct.Tactic.html#method.list_all). Tactics can
+/// be combined using tactic combinators such as
+/// [`Tactic::and_then()`](struct.Tactic.html#method.and_then).
+pub struct Tactic<'ctx> {
+    ctx: &'ctx Context,
+    z3_tactic: Z3_tactic,
+}
+
+/// Function that measures a [`Goal`](struct.Goal.html), used to decide
+/// which [`Tactic`](struct.Tactic.html) to apply.
+pub struct Probe<'ctx> {
+    ctx: &'ctx Context,
+    z3_probe: Z3_probe,
+}
+
+/// Collection of subgoals resulting from applying a
+/// [`Tactic`](struct.Tactic.html) to a [`Goal`](struct.Goal.html).
+pub struct ApplyResult<'ctx> {
+    ctx: &'ctx Context,
+    z3_apply_result: Z3_apply_result,
+}
+
 /// Build a datatype sort.
 ///
 /// Example:
 /// ```
-/// # use z3::{Config, Context, DatatypeBuilder, Solver};
+/// # use z3::{Config, Context, DatatypeBuilder, SatResult, Solver};
 /// # let cfg = Config::new();
 /// # let ctx = Context::new(&cfg);
 /// # let solver = Solver::new(&ctx);
@@ -129,7 +332,7 @@ pub struct Stats<'ctx> {
 /// let value = option_int.variants[1].constructor.apply(&[&ctx.from_i64(3)]);
 /// solver.assert(&y._eq(&value));
 ///
-/// assert!(solver.check());
+/// assert_eq!(solver.check(), SatResult::Sat);
 /// let model = solver.get_model();
 ///
 /// // Get the value out of Some(3)
@@ -138,8 +341,21 @@ pub struct Stats<'ctx> {
 /// ```
 pub struct DatatypeBuilder<'ctx> {
     ctx: &'ctx Context,
-    // num_fields and constructor
-    variants: Vec<(usize, Z3_constructor)>,
+    // name and fields of each variant
+    variants: Vec<(String, Vec<(String, FieldSort<'ctx>)>)>,
+}
+
+/// Sort of a field of a datatype variant, see
+/// [`DatatypeBuilder::variant_with()`](struct.DatatypeBuilder.html#method.variant_with).
+#[derive(Clone, Copy)]
+pub enum DatatypeAccessor<'a, 'ctx: 'a> {
+    /// A sort that already exists.
+    Sort(&'a Sort<'ctx>),
+    /// A datatype that is being built, by name.
+    Datatype(&'a str),
+    /// A datatype that is being built, by its index in
+    /// [`DatatypeBuilder::create_datatypes()`](struct.DatatypeBuilder.html#method.create_datatypes).
+    DatatypeIndex(usize),
 }
 
 pub struct DatatypeVariant<'ctx> {
@@ -1,42 +1,363 @@
+use std::vec;
 use z3_sys::*;
 use Ast;
+use AstVector;
+use Context;
+use FuncDecl;
+use FuncInterp;
 use Model;
 use Optimize;
 use Solver;
+use Sort;
+use Z3Error;
 
 impl<'ctx> Model<'ctx> {
+    /// Create an empty model, to be filled in with
+    /// [`Model::add_const_interp()`](#method.add_const_interp) and
+    /// [`Model::add_func_interp()`](#method.add_func_interp).
+    ///
+    /// ```
+    /// # use z3::{Ast, Config, Context, Model, Sort, Symbol};
+    /// let cfg = Config::new();
+    /// let ctx = Context::new(&cfg);
+    /// let x = Ast::new_const(&Symbol::from_string(&ctx, "x"), &Sort::int(&ctx));
+    /// let model = Model::new(&ctx);
+    /// model.add_const_interp(&x.decl(), &Ast::from_i64(&ctx, 3));
+    ///
+    /// let constraint = x.gt(&Ast::from_i64(&ctx, 2));
+    /// assert_eq!(model.eval(&constraint).unwrap().as_bool(), Some(true));
+    /// ```
+    pub fn new(ctx: &'ctx Context) -> Model<'ctx> {
+        unsafe { Model::from_raw(ctx, Z3_mk_model(ctx.z3_ctx)) }
+    }
+
+    pub(crate) unsafe fn from_raw(ctx: &'ctx Context, z3_mdl: Z3_model) -> Model<'ctx> {
+        Z3_model_inc_ref(ctx.z3_ctx, z3_mdl);
+        Model { ctx, z3_mdl }
+    }
+
     pub fn of_solver(slv: &Solver<'ctx>) -> Model<'ctx> {
-        Model {
-            ctx: slv.ctx,
-            z3_mdl: unsafe {
-                let m = Z3_solver_get_model(slv.ctx.z3_ctx, slv.z3_slv);
-                Z3_model_inc_ref(slv.ctx.z3_ctx, m);
-                m
-            },
+        Model::try_of_solver(slv).unwrap()
+    }
+
+    pub fn try_of_solver(slv: &Solver<'ctx>) -> Result<Model<'ctx>, Z3Error> {
+        unsafe {
+            let m = Z3_solver_get_model(slv.ctx.z3_ctx, slv.z3_slv);
+            slv.ctx.check_error()?;
+            Ok(Model::from_raw(slv.ctx, m))
         }
     }
 
     pub fn of_optimize(opt: &Optimize<'ctx>) -> Model<'ctx> {
-        Model {
-            ctx: opt.ctx,
-            z3_mdl: unsafe {
-                let m = Z3_optimize_get_model(opt.ctx.z3_ctx, opt.z3_opt);
-                Z3_model_inc_ref(opt.ctx.z3_ctx, m);
-                m
-            },
+        Model::try_of_optimize(opt).unwrap()
+    }
+
+    pub fn try_of_optimize(opt: &Optimize<'ctx>) -> Result<Model<'ctx>, Z3Error> {
+        unsafe {
+            let m = Z3_optimize_get_model(opt.ctx.z3_ctx, opt.z3_opt);
+            opt.ctx.check_error()?;
+            Ok(Model::from_raw(opt.ctx, m))
         }
     }
 
+    /// Copy the model into the context `dest`.
+    pub fn translate<'dest_ctx>(&self, dest: &'dest_ctx Context) -> Model<'dest_ctx> {
+        unsafe {
+            Model::from_raw(
+                dest,
+                Z3_model_translate(self.ctx.z3_ctx, self.z3_mdl, dest.z3_ctx),
+            )
+        }
+    }
+
+    /// Evaluate `ast` in the model, giving unconstrained constants a
+    /// default value.
+    ///
+    /// # See also:
+    ///
+    /// - [`Model::eval_with()`](#method.eval_with)
     pub fn eval(&self, ast: &Ast<'ctx>) -> Option<Ast<'ctx>> {
+        self.eval_with(ast, true)
+    }
+
+    /// Evaluate `ast` in the model.
+    ///
+    /// If `completion` is `false`, constants and functions that have no
+    /// interpretation in the model are left as they are in the result;
+    /// otherwise they are given a default value, and added to the model.
+    ///
+    /// ```
+    /// # use z3::{Ast, Config, Context, SatResult, Solver, Sort, Symbol};
+    /// let cfg = Config::new();
+    /// let ctx = Context::new(&cfg);
+    /// let x = Ast::new_const(&Symbol::from_string(&ctx, "x"), &Sort::int(&ctx));
+    /// let y = Ast::new_const(&Symbol::from_string(&ctx, "y"), &Sort::int(&ctx));
+    /// let solver = Solver::new(&ctx);
+    /// solver.assert(&x._eq(&Ast::from_i64(&ctx, 3)));
+    /// assert_eq!(solver.check(), SatResult::Sat);
+    ///
+    /// let model = solver.get_model();
+    /// assert_eq!(model.eval_with(&x, false).unwrap().as_i64(), Some(3));
+    /// assert_eq!(model.eval_with(&y, false).unwrap(), y);
+    /// assert!(model.eval_with(&y, true).unwrap().is_numeral());
+    /// ```
+    pub fn eval_with(&self, ast: &Ast<'ctx>, completion: bool) -> Option<Ast<'ctx>> {
         let mut tmp: Z3_ast = ast.z3_ast;
-        let res =
-            unsafe { Z3_model_eval(self.ctx.z3_ctx, self.z3_mdl, ast.z3_ast, true, &mut tmp) };
+        let res = unsafe {
+            Z3_model_eval(
+                self.ctx.z3_ctx,
+                self.z3_mdl,
+                ast.z3_ast,
+                completion,
+                &mut tmp,
+            )
+        };
         if res {
             Some(Ast::new(self.ctx, tmp))
         } else {
             None
         }
     }
+
+    /// Evaluate `ast` with completion, reporting an error if the model
+    /// cannot evaluate it.
+    fn eval_value(&self, ast: &Ast<'ctx>) -> Result<Ast<'ctx>, Z3Error> {
+        self.eval(ast).ok_or_else(|| {
+            Z3Error::with_message(Z3_INVALID_ARG, format!("cannot evaluate {}", ast))
+        })
+    }
+
+    /// Evaluate the Boolean term `ast` in the model.
+    ///
+    /// Reports an error if the value is not `true` or `false`.
+    pub fn get_bool(&self, ast: &Ast<'ctx>) -> Result<bool, Z3Error> {
+        let v = self.eval_value(ast)?;
+        v.as_bool()
+            .ok_or_else(|| value_error("a Boolean value", ast, &v))
+    }
+
+    /// Evaluate the integer or bitvector term `ast` in the model.
+    ///
+    /// Reports an error if the value is not a numeral that fits in an
+    /// `i64`. Bitvectors are read as unsigned.
+    pub fn get_i64(&self, ast: &Ast<'ctx>) -> Result<i64, Z3Error> {
+        let v = self.eval_value(ast)?;
+        v.as_i64()
+            .ok_or_else(|| value_error("an integer numeral that fits in an i64", ast, &v))
+    }
+
+    /// Evaluate the bitvector term `ast` in the model, as an unsigned
+    /// number.
+    ///
+    /// Reports an error if `ast` is not a bitvector of at most 128 bits.
+    pub fn get_bv_u128(&self, ast: &Ast<'ctx>) -> Result<u128, Z3Error> {
+        let v = self.eval_value(ast)?;
+        match v.sort().bv_size() {
+            Some(sz) if sz <= 128 => {}
+            _ => return Err(value_error("a bitvector of at most 128 bits", ast, &v)),
+        }
+        v.as_numeral_string()
+            .and_then(|s| s.parse().ok())
+            .ok_or_else(|| value_error("a bitvector numeral", ast, &v))
+    }
+
+    /// Evaluate the string term `ast` in the model.
+    ///
+    /// Characters that are not printable ASCII are returned escaped, as in
+    /// SMT-LIB2.
+    ///
+    /// Reports an error if the value is not a string literal.
+    pub fn get_string(&self, ast: &Ast<'ctx>) -> Result<String, Z3Error> {
+        let v = self.eval_value(ast)?;
+        v.as_string()
+            .ok_or_else(|| value_error("a string literal", ast, &v))
+    }
+
+    /// Evaluate the integer, real or floating-point term `ast` in the
+    /// model, as the closest `f64`.
+    ///
+    /// Reports an error if the value is not a numeral.
+    ///
+    /// # See also:
+    ///
+    /// - [`Ast::as_f64()`](struct.Ast.html#method.as_f64)
+    pub fn get_f64(&self, ast: &Ast<'ctx>) -> Result<f64, Z3Error> {
+        let v = self.eval_value(ast)?;
+        v.as_f64()
+            .ok_or_else(|| value_error("a rational or floating-point numeral", ast, &v))
+    }
+
+    /// Return the declarations of the constants that have an
+    /// interpretation in the model.
+    pub fn get_const_decls(&self) -> Vec<FuncDecl<'ctx>> {
+        let n = unsafe { Z3_model_get_num_consts(self.ctx.z3_ctx, self.z3_mdl) };
+        (0..n)
+            .map(|i| unsafe {
+                FuncDecl::from_raw(
+                    self.ctx,
+                    Z3_model_get_const_decl(self.ctx.z3_ctx, self.z3_mdl, i),
+                )
+            })
+            .collect()
+    }
+
+    /// Return the declarations of the functions (of arity greater than
+    /// zero) that have an interpretation in the model.
+    pub fn get_func_decls(&self) -> Vec<FuncDecl<'ctx>> {
+        let n = unsafe { Z3_model_get_num_funcs(self.ctx.z3_ctx, self.z3_mdl) };
+        (0..n)
+            .map(|i| unsafe {
+                FuncDecl::from_raw(
+                    self.ctx,
+                    Z3_model_get_func_decl(self.ctx.z3_ctx, self.z3_mdl, i),
+                )
+            })
+            .collect()
+    }
+
+    /// Iterate over the declarations of all constants and functions that
+    /// have an interpretation in the model, constants first.
+    ///
+    /// ```
+    /// # use z3::{Ast, Config, Context, SatResult, Solver, Sort, Symbol};
+    /// let cfg = Config::new();
+    /// let ctx = Context::new(&cfg);
+    /// let solver = Solver::new(&ctx);
+    /// let x = Ast::new_const(&Symbol::from_string(&ctx, "x"), &Sort::int(&ctx));
+    /// solver.assert(&x._eq(&Ast::from_i64(&ctx, 3)));
+    /// assert_eq!(solver.check(), SatResult::Sat);
+    ///
+    /// let model = solver.get_model();
+    /// for decl in model.iter() {
+    ///     let value = model.get_const_interp(&decl).unwrap();
+    ///     assert_eq!(format!("{} = {}", decl.name(), value), "x = 3");
+    /// }
+    /// ```
+    pub fn iter(&self) -> vec::IntoIter<FuncDecl<'ctx>> {
+        let mut decls = self.get_const_decls();
+        decls.extend(self.get_func_decls());
+        decls.into_iter()
+    }
+
+    /// Return `true` if the model has an interpretation for `decl`.
+    pub fn has_interp(&self, decl: &FuncDecl<'ctx>) -> bool {
+        unsafe { Z3_model_has_interp(self.ctx.z3_ctx, self.z3_mdl, decl.z3_func_decl) }
+    }
+
+    /// Return the value of the constant `decl` in the model, or `None` if
+    /// the model has no interpretation for it.
+    ///
+    /// Panics if `decl` is not a constant, i.e. if its arity is not zero.
+    pub fn get_const_interp(&self, decl: &FuncDecl<'ctx>) -> Option<Ast<'ctx>> {
+        assert_eq!(decl.arity(), 0);
+        let v =
+            unsafe { Z3_model_get_const_interp(self.ctx.z3_ctx, self.z3_mdl, decl.z3_func_decl) };
+        if v.is_null() {
+            None
+        } else {
+            Some(Ast::new(self.ctx, v))
+        }
+    }
+
+    /// Return the interpretation of the function `decl` in the model, or
+    /// `None` if the model has no interpretation for it.
+    ///
+    /// Panics if `decl` is a constant, i.e. if its arity is zero.
+    pub fn get_func_interp(&self, decl: &FuncDecl<'ctx>) -> Option<FuncInterp<'ctx>> {
+        assert_ne!(decl.arity(), 0);
+        let f =
+            unsafe { Z3_model_get_func_interp(self.ctx.z3_ctx, self.z3_mdl, decl.z3_func_decl) };
+        if f.is_null() {
+            None
+        } else {
+            Some(unsafe { FuncInterp::from_raw(self.ctx, f) })
+        }
+    }
+
+    /// Set the value of the constant `decl` to `value`.
+    ///
+    /// Panics if `decl` is not a constant, i.e. if its arity is not zero.
+    pub fn add_const_interp(&self, decl: &FuncDecl<'ctx>, value: &Ast<'ctx>) {
+        assert_eq!(decl.arity(), 0);
+        unsafe {
+            Z3_add_const_interp(
+                self.ctx.z3_ctx,
+                self.z3_mdl,
+                decl.z3_func_decl,
+                value.z3_ast,
+            )
+        };
+    }
+
+    /// Add an interpretation for the function `decl` that maps all
+    /// arguments to `default_value`. Entries for particular arguments can
+    /// be added with [`FuncInterp::add_entry()`](struct.FuncInterp.html#method.add_entry).
+    ///
+    /// Panics if `decl` is a constant, i.e. if its arity is zero.
+    pub fn add_func_interp(
+        &self,
+        decl: &FuncDecl<'ctx>,
+        default_value: &Ast<'ctx>,
+    ) -> FuncInterp<'ctx> {
+        assert_ne!(decl.arity(), 0);
+        unsafe {
+            FuncInterp::from_raw(
+                self.ctx,
+                Z3_add_func_interp(
+                    self.ctx.z3_ctx,
+                    self.z3_mdl,
+                    decl.z3_func_decl,
+                    default_value.z3_ast,
+                ),
+            )
+        }
+    }
+
+    /// Return the uninterpreted sorts that have a finite universe in the
+    /// model.
+    pub fn get_sorts(&self) -> Vec<Sort<'ctx>> {
+        let n = unsafe { Z3_model_get_num_sorts(self.ctx.z3_ctx, self.z3_mdl) };
+        (0..n)
+            .map(|i| unsafe {
+                Sort::from_raw(self.ctx, Z3_model_get_sort(self.ctx.z3_ctx, self.z3_mdl, i))
+            })
+            .collect()
+    }
+
+    /// Return the elements of the uninterpreted sort `sort` in the model,
+    /// or `None` if `sort` is not one of
+    /// [`Model::get_sorts()`](#method.get_sorts).
+    pub fn get_sort_universe(&self, sort: &Sort<'ctx>) -> Option<Vec<Ast<'ctx>>> {
+        let v = unsafe { Z3_model_get_sort_universe(self.ctx.z3_ctx, self.z3_mdl, sort.z3_sort) };
+        if self.ctx.check_error().is_err() || v.is_null() {
+            return None;
+        }
+        Some(unsafe { AstVector::from_raw(self.ctx, v) }.to_vec())
+    }
+}
+
+fn value_error(expected: &str, ast: &Ast, value: &Ast) -> Z3Error {
+    Z3Error::with_message(
+        Z3_INVALID_ARG,
+        format!("expected {} for {}, got {}", expected, ast, value),
+    )
+}
+
+impl<'ctx> IntoIterator for &Model<'ctx> {
+    type Item = FuncDecl<'ctx>;
+    type IntoIter = vec::IntoIter<FuncDecl<'ctx>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'ctx> Clone for Model<'ctx> {
+    /// Return a copy of the model; changes to either model do not affect
+    /// the other.
+    fn clone(&self) -> Model<'ctx> {
+        self.translate(self.ctx)
+    }
 }
 
 impl<'ctx> std::fmt::Display for Model<'ctx> {
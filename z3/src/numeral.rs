@@ -0,0 +1,100 @@
+//! Conversions between Z3 numerals and the arbitrary-size number types of
+//! `num-bigint` and `num-rational`.
+//!
+//! Requires the `arbitrary-size-numeral` feature.
+
+use ast::{Int, Real, TypedAst, BV};
+use num_bigint::{BigInt, BigUint};
+use num_rational::BigRational;
+use std::convert::TryFrom;
+use std::str::FromStr;
+use z3_sys::*;
+use {Ast, Context, Z3Error};
+
+impl<'ctx> Ast<'ctx> {
+    /// Create an integer numeral from a `BigInt`.
+    pub fn from_big_int(ctx: &'ctx Context, value: &BigInt) -> Ast<'ctx> {
+        Ast::from_str_numeral(&ctx.int_sort(), &value.to_string())
+    }
+
+    /// Create an integer numeral from a `BigUint`.
+    pub fn from_big_uint(ctx: &'ctx Context, value: &BigUint) -> Ast<'ctx> {
+        Ast::from_str_numeral(&ctx.int_sort(), &value.to_string())
+    }
+
+    /// Create a real numeral from a `BigRational`.
+    pub fn from_big_rational(ctx: &'ctx Context, value: &BigRational) -> Ast<'ctx> {
+        let s = format!("{}/{}", value.numer(), value.denom());
+        Ast::from_str_numeral(&ctx.real_sort(), &s)
+    }
+
+    /// Return the value of an integer or bit-vector numeral.
+    pub fn as_big_int(&self) -> Option<BigInt> {
+        BigInt::from_str(&self.as_numeral_string()?).ok()
+    }
+
+    /// Return the value of a non-negative integer or bit-vector numeral.
+    pub fn as_big_uint(&self) -> Option<BigUint> {
+        BigUint::from_str(&self.as_numeral_string()?).ok()
+    }
+
+    /// Return the value of an integer or real numeral.
+    pub fn as_big_rational(&self) -> Option<BigRational> {
+        BigRational::from_str(&self.as_numeral_string()?).ok()
+    }
+}
+
+impl<'ctx> Int<'ctx> {
+    pub fn from_big_int(ctx: &'ctx Context, value: &BigInt) -> Int<'ctx> {
+        Int::from_ast(&Ast::from_big_int(ctx, value)).unwrap()
+    }
+
+    pub fn as_big_int(&self) -> Option<BigInt> {
+        self.as_ast().as_big_int()
+    }
+}
+
+impl<'ctx> Real<'ctx> {
+    pub fn from_big_rational(ctx: &'ctx Context, value: &BigRational) -> Real<'ctx> {
+        Real::from_ast(&Ast::from_big_rational(ctx, value)).unwrap()
+    }
+
+    pub fn as_big_rational(&self) -> Option<BigRational> {
+        self.as_ast().as_big_rational()
+    }
+}
+
+impl<'ctx> BV<'ctx> {
+    /// Create a bit-vector numeral of width `sz`. Negative values are
+    /// represented in two's complement.
+    pub fn from_big_int(ctx: &'ctx Context, value: &BigInt, sz: u32) -> BV<'ctx> {
+        let ast = Ast::from_str_numeral(&ctx.bitvector_sort(sz), &value.to_string());
+        BV::from_ast(&ast).unwrap()
+    }
+
+    /// Return the value of a bit-vector numeral, read as an unsigned number.
+    pub fn as_big_uint(&self) -> Option<BigUint> {
+        self.as_ast().as_big_uint()
+    }
+}
+
+macro_rules! try_from_numeral {
+    ( $T:ty, $f:ident ) => {
+        impl<'a, 'ctx> TryFrom<&'a Ast<'ctx>> for $T {
+            type Error = Z3Error;
+
+            fn try_from(ast: &'a Ast<'ctx>) -> Result<$T, Z3Error> {
+                ast.$f().ok_or_else(|| {
+                    Z3Error::with_message(
+                        Z3_INVALID_ARG,
+                        format!("{} is not a numeral of type {}", ast, stringify!($T)),
+                    )
+                })
+            }
+        }
+    };
+}
+
+try_from_numeral!(BigInt, as_big_int);
+try_from_numeral!(BigUint, as_big_uint);
+try_from_numeral!(BigRational, as_big_rational);
@@ -1,10 +1,11 @@
-use std::ffi::CStr;
+use std::ffi::{CStr, CString};
 use std::fmt;
 use z3_sys::*;
 use Context;
 use Model;
 use Optimize;
-use {Ast, ParamDescrs};
+use SatResult;
+use {Ast, AstVector, Objective, ParamDescrs, Params, Stats, Symbol, Z3Error};
 
 impl<'ctx> Optimize<'ctx> {
     /// Create a new optimize context.
@@ -26,27 +27,205 @@ impl<'ctx> Optimize<'ctx> {
     /// - [`Optimize::maximize()`](#method.maximize)
     /// - [`Optimize::minimize()`](#method.minimize)
     pub fn assert(&self, ast: &Ast<'ctx>) {
+        self.try_assert(ast).unwrap()
+    }
+
+    /// Like [`Optimize::assert()`](#method.assert), but reports an error
+    /// instead of panicking if `ast` has the wrong sort.
+    pub fn try_assert(&self, ast: &Ast<'ctx>) -> Result<(), Z3Error> {
         unsafe { Z3_optimize_assert(self.ctx.z3_ctx, self.z3_opt, ast.z3_ast) };
+        self.ctx.check_error()
+    }
+
+    /// Assert a hard constraint `ast` and track it with the Boolean
+    /// constant `p`, which then appears in
+    /// [`Optimize::get_unsat_core()`](#method.get_unsat_core) when `ast` is
+    /// part of the reason for unsatisfiability.
+    ///
+    /// # See also:
+    ///
+    /// - [`Solver::assert_and_track()`](struct.Solver.html#method.assert_and_track)
+    pub fn assert_and_track(&self, ast: &Ast<'ctx>, p: &Ast<'ctx>) {
+        self.try_assert_and_track(ast, p).unwrap()
+    }
+
+    /// Like [`Optimize::assert_and_track()`](#method.assert_and_track), but
+    /// reports an error instead of panicking if `ast` or `p` is not a
+    /// Boolean term.
+    pub fn try_assert_and_track(&self, ast: &Ast<'ctx>, p: &Ast<'ctx>) -> Result<(), Z3Error> {
+        unsafe { Z3_optimize_assert_and_track(self.ctx.z3_ctx, self.z3_opt, ast.z3_ast, p.z3_ast) };
+        self.ctx.check_error()
+    }
+
+    /// Assert a soft constraint: the optimizer minimizes the total
+    /// `weight` of the violated soft constraints in each `group`.
+    ///
+    /// `weight` must print as a non-negative decimal number, e.g. `3` or
+    /// `0.5`. Soft constraints without a group share a default group.
+    /// Returns the handle of the objective of the group.
+    ///
+    /// ```
+    /// # use z3::{Ast, Config, Context, Optimize, SatResult, Sort, Symbol};
+    /// let cfg = Config::new();
+    /// let ctx = Context::new(&cfg);
+    /// let a = Ast::new_const(&Symbol::from_string(&ctx, "a"), &Sort::bool(&ctx));
+    /// let b = Ast::new_const(&Symbol::from_string(&ctx, "b"), &Sort::bool(&ctx));
+    /// let opt = Optimize::new(&ctx);
+    /// opt.assert(&a.and(&[&b]).not());
+    /// opt.assert_soft(&a, 1, None);
+    /// let cost = opt.assert_soft(&b, 2, None);
+    /// assert_eq!(opt.check(), SatResult::Sat);
+    ///
+    /// assert_eq!(opt.get_model().get_bool(&b), Ok(true));
+    /// assert_eq!(opt.get_upper(cost).as_i64(), Some(1));
+    /// ```
+    pub fn assert_soft<W: fmt::Display>(
+        &self,
+        ast: &Ast<'ctx>,
+        weight: W,
+        group: Option<&str>,
+    ) -> Objective {
+        self.try_assert_soft(ast, weight, group).unwrap()
+    }
+
+    /// Like [`Optimize::assert_soft()`](#method.assert_soft), but reports
+    /// an error instead of panicking if `ast` is not a Boolean term or
+    /// `weight` is not a valid weight.
+    pub fn try_assert_soft<W: fmt::Display>(
+        &self,
+        ast: &Ast<'ctx>,
+        weight: W,
+        group: Option<&str>,
+    ) -> Result<Objective, Z3Error> {
+        let weight = CString::new(weight.to_string())
+            .map_err(|e| Z3Error::with_message(Z3_INVALID_ARG, format!("invalid weight: {}", e)))?;
+        let group = Symbol::from_string(self.ctx, group.unwrap_or(""));
+        let index = unsafe {
+            Z3_optimize_assert_soft(
+                self.ctx.z3_ctx,
+                self.z3_opt,
+                ast.z3_ast,
+                weight.as_ptr(),
+                group.z3_sym,
+            )
+        };
+        self.ctx.check_error()?;
+        Ok(Objective {
+            z3_opt: self.z3_opt,
+            index,
+        })
     }
 
-    /// Add a maximization constraint.
+    /// Add a maximization constraint, returning the handle of the new
+    /// objective.
     ///
     /// # See also:
     ///
     /// - [`Optimize::assert()`](#method.assert)
     /// - [`Optimize::minimize()`](#method.minimize)
-    pub fn maximize(&self, ast: &Ast<'ctx>) {
-        unsafe { Z3_optimize_maximize(self.ctx.z3_ctx, self.z3_opt, ast.z3_ast) };
+    pub fn maximize(&self, ast: &Ast<'ctx>) -> Objective {
+        self.try_maximize(ast).unwrap()
+    }
+
+    /// Like [`Optimize::maximize()`](#method.maximize), but reports an error
+    /// instead of panicking if `ast` has the wrong sort.
+    pub fn try_maximize(&self, ast: &Ast<'ctx>) -> Result<Objective, Z3Error> {
+        let index = unsafe { Z3_optimize_maximize(self.ctx.z3_ctx, self.z3_opt, ast.z3_ast) };
+        self.ctx.check_error()?;
+        Ok(Objective {
+            z3_opt: self.z3_opt,
+            index,
+        })
     }
 
-    /// Add a minimization constraint.
+    /// Add a minimization constraint, returning the handle of the new
+    /// objective.
     ///
     /// # See also:
     ///
     /// - [`Optimize::assert()`](#method.assert)
     /// - [`Optimize::maximize()`](#method.maximize)
-    pub fn minimize(&self, ast: &Ast<'ctx>) {
-        unsafe { Z3_optimize_minimize(self.ctx.z3_ctx, self.z3_opt, ast.z3_ast) };
+    pub fn minimize(&self, ast: &Ast<'ctx>) -> Objective {
+        self.try_minimize(ast).unwrap()
+    }
+
+    /// Like [`Optimize::minimize()`](#method.minimize), but reports an error
+    /// instead of panicking if `ast` has the wrong sort.
+    pub fn try_minimize(&self, ast: &Ast<'ctx>) -> Result<Objective, Z3Error> {
+        let index = unsafe { Z3_optimize_minimize(self.ctx.z3_ctx, self.z3_opt, ast.z3_ast) };
+        self.ctx.check_error()?;
+        Ok(Objective {
+            z3_opt: self.z3_opt,
+            index,
+        })
+    }
+
+    /// Return the lower bound of `objective` found by the last
+    /// [`Optimize::check()`](#method.check). It is the optimum if the
+    /// check was conclusive.
+    ///
+    /// For a soft constraint group, the bound is on the total weight of
+    /// the violated constraints.
+    ///
+    /// Panics if `objective` was not created by this context.
+    pub fn get_lower(&self, objective: Objective) -> Ast<'ctx> {
+        self.try_get_lower(objective).unwrap()
+    }
+
+    /// Like [`Optimize::get_lower()`](#method.get_lower), but reports an
+    /// error instead of panicking if `objective` was not created by this
+    /// context.
+    pub fn try_get_lower(&self, objective: Objective) -> Result<Ast<'ctx>, Z3Error> {
+        self.check_objective(objective)?;
+        Ast::try_new(self.ctx, unsafe {
+            Z3_optimize_get_lower(self.ctx.z3_ctx, self.z3_opt, objective.index)
+        })
+    }
+
+    /// Return the upper bound of `objective` found by the last
+    /// [`Optimize::check()`](#method.check). It is the optimum if the
+    /// check was conclusive.
+    ///
+    /// For a soft constraint group, the bound is on the total weight of
+    /// the violated constraints.
+    ///
+    /// Panics if `objective` was not created by this context.
+    pub fn get_upper(&self, objective: Objective) -> Ast<'ctx> {
+        self.try_get_upper(objective).unwrap()
+    }
+
+    /// Like [`Optimize::get_upper()`](#method.get_upper), but reports an
+    /// error instead of panicking if `objective` was not created by this
+    /// context.
+    pub fn try_get_upper(&self, objective: Objective) -> Result<Ast<'ctx>, Z3Error> {
+        self.check_objective(objective)?;
+        Ast::try_new(self.ctx, unsafe {
+            Z3_optimize_get_upper(self.ctx.z3_ctx, self.z3_opt, objective.index)
+        })
+    }
+
+    fn check_objective(&self, objective: Objective) -> Result<(), Z3Error> {
+        if objective.z3_opt == self.z3_opt {
+            Ok(())
+        } else {
+            Err(Z3Error::with_message(
+                Z3_INVALID_ARG,
+                "objective of another optimize context".to_owned(),
+            ))
+        }
+    }
+
+    /// Return the terms of all objectives, in the order in which they were
+    /// added. Soft constraint groups appear as the sum of the weights of
+    /// their violated constraints.
+    pub fn get_objectives(&self) -> Vec<Ast<'ctx>> {
+        unsafe {
+            AstVector::from_raw(
+                self.ctx,
+                Z3_optimize_get_objectives(self.ctx.z3_ctx, self.z3_opt),
+            )
+        }
+        .to_vec()
     }
 
     /// Create a backtracking point.
@@ -73,7 +252,14 @@ impl<'ctx> Optimize<'ctx> {
     ///
     /// - [`Optimize::push()`](#method.push)
     pub fn pop(&self) {
+        self.try_pop().unwrap()
+    }
+
+    /// Backtrack one level, reporting an error if there is no
+    /// backtracking point.
+    pub fn try_pop(&self) -> Result<(), Z3Error> {
         unsafe { Z3_optimize_pop(self.ctx.z3_ctx, self.z3_opt) };
+        self.ctx.check_error()
     }
 
     /// Check consistency and produce optimal values.
@@ -81,19 +267,111 @@ impl<'ctx> Optimize<'ctx> {
     /// # See also:
     ///
     /// - [`Optimize::get_model()`](#method.get_model)
-    pub fn check(&self) -> bool {
-        unsafe { Z3_optimize_check(self.ctx.z3_ctx, self.z3_opt) == Z3_L_TRUE }
+    pub fn check(&self) -> SatResult {
+        self.check_assumptions(&[])
+    }
+
+    /// Like [`Optimize::check()`](#method.check), but additionally assume
+    /// the Boolean terms `assumptions`.
+    ///
+    /// If the result is [`SatResult::Unsat`](enum.SatResult.html#variant.Unsat),
+    /// [`Optimize::get_unsat_core()`](#method.get_unsat_core) returns the
+    /// assumptions that were used to derive it.
+    pub fn check_assumptions(&self, assumptions: &[Ast<'ctx>]) -> SatResult {
+        let a: Vec<Z3_ast> = assumptions.iter().map(|a| a.z3_ast).collect();
+        match unsafe { Z3_optimize_check(self.ctx.z3_ctx, self.z3_opt, a.len() as u32, a.as_ptr()) }
+        {
+            Z3_L_FALSE => SatResult::Unsat,
+            Z3_L_UNDEF => SatResult::Unknown,
+            Z3_L_TRUE => SatResult::Sat,
+        }
+    }
+
+    /// Return the subset of the assumptions and tracking constants that
+    /// was used to show unsatisfiability in the last
+    /// [`Optimize::check_assumptions()`](#method.check_assumptions).
+    ///
+    /// # See also:
+    ///
+    /// - [`Optimize::assert_and_track()`](#method.assert_and_track)
+    pub fn get_unsat_core(&self) -> Vec<Ast<'ctx>> {
+        unsafe {
+            AstVector::from_raw(
+                self.ctx,
+                Z3_optimize_get_unsat_core(self.ctx.z3_ctx, self.z3_opt),
+            )
+        }
+        .to_vec()
     }
 
     /// Retrieve the model for the last [`Optimize::check()`](#method.check)
     ///
-    /// The error handler is invoked if a model is not available because
-    /// the commands above were not invoked for the given optimization
-    /// solver, or if the result was `Z3_L_FALSE`.
+    /// Panics if a model is not available because the commands above
+    /// were not invoked for the given optimization solver, or if the
+    /// result was [`SatResult::Unsat`](enum.SatResult.html#variant.Unsat).
+    ///
+    /// # See also:
+    ///
+    /// - [`Optimize::try_get_model()`](#method.try_get_model)
     pub fn get_model(&self) -> Model<'ctx> {
         Model::of_optimize(self)
     }
 
+    /// Retrieve the model for the last [`Optimize::check()`](#method.check),
+    /// reporting an error if no model is available.
+    pub fn try_get_model(&self) -> Result<Model<'ctx>, Z3Error> {
+        Model::try_of_optimize(self)
+    }
+
+    /// Retrieve a string that describes the last status returned by
+    /// [`Optimize::check()`](#method.check).
+    ///
+    /// Use this method when [`Optimize::check()`](#method.check) returns
+    /// [`SatResult::Unknown`](enum.SatResult.html#variant.Unknown).
+    pub fn get_reason_unknown(&self) -> Option<String> {
+        let p = unsafe { Z3_optimize_get_reason_unknown(self.ctx.z3_ctx, self.z3_opt) };
+        if p.is_null() {
+            return None;
+        }
+        unsafe { CStr::from_ptr(p) }
+            .to_str()
+            .ok()
+            .map(|s| s.to_string())
+    }
+
+    /// Return a standalone SMT-LIB2 script with the declarations,
+    /// assertions (including soft constraints) and objectives of this
+    /// context, followed by `(check-sat)`.
+    ///
+    /// The script can be replayed with the `z3` command line tool.
+    pub fn to_smt2(&self) -> String {
+        unsafe {
+            let s = Z3_optimize_to_string(self.ctx.z3_ctx, self.z3_opt);
+            CStr::from_ptr(s).to_string_lossy().into_owned()
+        }
+    }
+
+    /// Set the parameters of the optimizer.
+    ///
+    /// Panics if a parameter is unknown or has the wrong type; see
+    /// [`Optimize::get_param_descrs()`](#method.get_param_descrs).
+    pub fn set_params(&self, params: &Params<'ctx>) {
+        self.try_set_params(params).unwrap()
+    }
+
+    /// Set the parameters of the optimizer, reporting an error if a
+    /// parameter is unknown or has the wrong type.
+    pub fn try_set_params(&self, params: &Params<'ctx>) -> Result<(), Z3Error> {
+        unsafe { Z3_optimize_set_params(self.ctx.z3_ctx, self.z3_opt, params.z3_params) };
+        self.ctx.check_error()
+    }
+
+    /// Return statistics about the last
+    /// [`Optimize::check()`](#method.check).
+    pub fn get_statistics(&self) -> Stats<'ctx> {
+        Stats::with_optimize(self)
+    }
+
     pub fn get_help(&self) -> String {
         unsafe {
             let s = Z3_optimize_get_help(self.ctx.z3_ctx, self.z3_opt);
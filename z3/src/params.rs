@@ -1,7 +1,7 @@
 use std::convert::TryInto;
 use z3_sys::*;
 use ParamDescrs;
-use {Context, Params, Symbol};
+use {Context, Params, Solver, SolverParams, Symbol, Z3Error};
 
 impl<'ctx> Params<'ctx> {
     pub fn new(ctx: &'ctx Context) -> Self {
@@ -44,12 +44,96 @@ impl<'ctx> Params<'ctx> {
         unsafe { Z3_params_set_symbol(self.ctx.z3_ctx, self.z3_params, k.z3_sym, v.z3_sym) }
     }
 
+    /// Set the string or symbol parameter `k` to `v`.
+    pub fn set_str(&self, k: &Symbol<'ctx>, v: &str) {
+        self.set_symbol(k, &Symbol::from_string(self.ctx, v))
+    }
+
     pub fn validate(&self, d: &ParamDescrs<'ctx>) {
+        self.try_validate(d).unwrap()
+    }
+
+    /// Check that every parameter in `self` is described by `d` and has
+    /// the expected type.
+    pub fn try_validate(&self, d: &ParamDescrs<'ctx>) -> Result<(), Z3Error> {
         assert_eq!(self.ctx.z3_ctx, d.ctx.z3_ctx);
 
         unsafe {
             Z3_params_validate(self.ctx.z3_ctx, self.z3_params, d.z3_param_descrs);
         }
+        self.ctx.check_error()
+    }
+}
+
+impl<'ctx> SolverParams<'ctx> {
+    pub fn new(ctx: &'ctx Context) -> Self {
+        Self {
+            params: Params::new(ctx),
+        }
+    }
+
+    /// Give up on each check after `ms` milliseconds.
+    pub fn timeout_msec(self, ms: usize) -> Self {
+        self.uint("timeout", ms)
+    }
+
+    /// Give up on each check after `limit` resource units, which unlike
+    /// a timeout is deterministic.
+    pub fn rlimit(self, limit: usize) -> Self {
+        self.uint("rlimit", limit)
+    }
+
+    pub fn random_seed(self, seed: usize) -> Self {
+        self.uint("random_seed", seed)
+    }
+
+    /// Enable or disable the computation of unsat cores.
+    pub fn unsat_core(self, b: bool) -> Self {
+        self.bool("unsat_core", b)
+    }
+
+    /// Enable or disable model-based quantifier instantiation.
+    pub fn mbqi(self, b: bool) -> Self {
+        self.bool("mbqi", b)
+    }
+
+    /// Set the SMT-LIB2 logic of the assertions, e.g. `QF_LIA`.
+    pub fn logic(self, logic: &str) -> Self {
+        self.params
+            .set_str(&Symbol::from_string(self.params.ctx, "logic"), logic);
+        self
+    }
+
+    fn uint(self, k: &str, v: usize) -> Self {
+        self.params
+            .set_uint(&Symbol::from_string(self.params.ctx, k), v);
+        self
+    }
+
+    fn bool(self, k: &str, v: bool) -> Self {
+        self.params
+            .set_bool(&Symbol::from_string(self.params.ctx, k), v);
+        self
+    }
+
+    /// Return the parameters set so far, e.g. to set parameters that have
+    /// no method of their own.
+    pub fn params(&self) -> &Params<'ctx> {
+        &self.params
+    }
+
+    /// Set the parameters on `solver`.
+    ///
+    /// Panics if a parameter is unknown to the solver or has the wrong
+    /// type; see [`SolverParams::try_apply()`](#method.try_apply).
+    pub fn apply(&self, solver: &Solver<'ctx>) {
+        self.try_apply(solver).unwrap()
+    }
+
+    /// Set the parameters on `solver`, reporting an error if a parameter
+    /// is unknown to the solver or has the wrong type.
+    pub fn try_apply(&self, solver: &Solver<'ctx>) -> Result<(), Z3Error> {
+        solver.try_set_params(&self.params)
     }
 }
 
@@ -0,0 +1,98 @@
+use error::c_string;
+use std::ffi::{CStr, CString};
+use z3_sys::*;
+use {Context, Goal, Probe, Z3Error};
+
+macro_rules! binop {
+    ( $f:ident, $z3fn:ident ) => {
+        pub fn $f(&self, other: &Probe<'ctx>) -> Probe<'ctx> {
+            unsafe {
+                Probe::from_raw(
+                    self.ctx,
+                    $z3fn(self.ctx.z3_ctx, self.z3_probe, other.z3_probe),
+                )
+            }
+        }
+    };
+}
+
+impl<'ctx> Probe<'ctx> {
+    /// Return the names of all probes supported by Z3.
+    pub fn list_all(ctx: &'ctx Context) -> Vec<String> {
+        let n = unsafe { Z3_get_num_probes(ctx.z3_ctx) };
+        (0..n)
+            .map(|i| unsafe {
+                CStr::from_ptr(Z3_get_probe_name(ctx.z3_ctx, i))
+                    .to_string_lossy()
+                    .into_owned()
+            })
+            .collect()
+    }
+
+    /// Return a description of the probe called `name`.
+    pub fn describe(ctx: &'ctx Context, name: &str) -> String {
+        let name = CString::new(name).unwrap();
+        unsafe {
+            CStr::from_ptr(Z3_probe_get_descr(ctx.z3_ctx, name.as_ptr()))
+                .to_string_lossy()
+                .into_owned()
+        }
+    }
+
+    /// Create the probe called `name`, e.g. `"num-consts"` or `"is-qfbv"`.
+    ///
+    /// Panics if there is no such probe; see
+    /// [`Probe::list_all()`](#method.list_all).
+    pub fn new(ctx: &'ctx Context, name: &str) -> Probe<'ctx> {
+        Probe::try_new(ctx, name).unwrap()
+    }
+
+    /// Create the probe called `name`, reporting an error if there is no
+    /// such probe.
+    pub fn try_new(ctx: &'ctx Context, name: &str) -> Result<Probe<'ctx>, Z3Error> {
+        let name = c_string(name, Z3_INVALID_ARG)?;
+        let p = unsafe { Z3_mk_probe(ctx.z3_ctx, name.as_ptr()) };
+        ctx.check_error()?;
+        Ok(unsafe { Probe::from_raw(ctx, p) })
+    }
+
+    pub(crate) unsafe fn from_raw(ctx: &'ctx Context, z3_probe: Z3_probe) -> Probe<'ctx> {
+        Z3_probe_inc_ref(ctx.z3_ctx, z3_probe);
+        Probe { ctx, z3_probe }
+    }
+
+    /// Create a probe that always evaluates to `val`.
+    pub fn constant(ctx: &'ctx Context, val: f64) -> Probe<'ctx> {
+        unsafe { Probe::from_raw(ctx, Z3_probe_const(ctx.z3_ctx, val)) }
+    }
+
+    /// Evaluate the probe on `goal`. Boolean probes return `0.0` for false
+    /// and `1.0` for true.
+    pub fn apply(&self, goal: &Goal<'ctx>) -> f64 {
+        unsafe { Z3_probe_apply(self.ctx.z3_ctx, self.z3_probe, goal.z3_goal) }
+    }
+
+    binop!(lt, Z3_probe_lt);
+    binop!(gt, Z3_probe_gt);
+    binop!(le, Z3_probe_le);
+    binop!(ge, Z3_probe_ge);
+    binop!(_eq, Z3_probe_eq);
+    binop!(and, Z3_probe_and);
+    binop!(or, Z3_probe_or);
+
+    pub fn not(&self) -> Probe<'ctx> {
+        unsafe { Probe::from_raw(self.ctx, Z3_probe_not(self.ctx.z3_ctx, self.z3_probe)) }
+    }
+}
+
+impl<'ctx> Clone for Probe<'ctx> {
+    fn clone(&self) -> Probe<'ctx> {
+        unsafe { Probe::from_raw(self.ctx, self.z3_probe) }
+    }
+}
+
+impl<'ctx> Drop for Probe<'ctx> {
+    fn drop(&mut self) {
+        unsafe { Z3_probe_dec_ref(self.ctx.z3_ctx, self.z3_probe) };
+    }
+}
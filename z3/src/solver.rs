@@ -1,10 +1,20 @@
-use std::ffi::CStr;
+use error::{c_path, c_string};
+use std::ffi::{CStr, CString};
 use std::fmt;
+use std::panic::{self, AssertUnwindSafe};
+use std::path::Path;
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::thread;
+use std::time::Duration;
 use z3_sys::*;
+use Config;
 use Context;
+use ContextHandle;
 use Model;
+use SatResult;
 use Solver;
-use {Ast, ParamDescrs};
+use Tactic;
+use {Ast, AstVector, ParamDescrs, Params, PortfolioConfig, SolverModels, Stats, Symbol, Z3Error};
 
 impl<'ctx> Solver<'ctx> {
     /// Create a new solver. This solver is a "combined solver"
@@ -56,6 +66,87 @@ impl<'ctx> Solver<'ctx> {
         }
     }
 
+    /// Create a solver customized for the SMT-LIB2 logic `logic`, e.g.
+    /// `QF_BV` or `QF_FD`.
+    ///
+    /// Panics if the logic is not supported; see
+    /// [`Solver::try_new_for_logic()`](#method.try_new_for_logic).
+    pub fn new_for_logic(ctx: &'ctx Context, logic: &str) -> Solver<'ctx> {
+        Solver::try_new_for_logic(ctx, logic).unwrap()
+    }
+
+    /// Like [`Solver::new_for_logic()`](#method.new_for_logic), but reports
+    /// an error instead of panicking if the logic is not supported.
+    pub fn try_new_for_logic(ctx: &'ctx Context, logic: &str) -> Result<Solver<'ctx>, Z3Error> {
+        let logic = Symbol::from_string(ctx, logic);
+        let s = unsafe { Z3_mk_solver_for_logic(ctx.z3_ctx, logic.z3_sym) };
+        ctx.check_error()?;
+        unsafe { Z3_solver_inc_ref(ctx.z3_ctx, s) };
+        Ok(Solver { ctx, z3_slv: s })
+    }
+
+    /// Create a solver that is implemented using the given tactic.
+    ///
+    /// [`Solver::push()`](#method.push) and [`Solver::pop()`](#method.pop)
+    /// are supported, but the solver is not incremental: every check
+    /// applies the tactic to all current assertions from scratch.
+    pub fn from_tactic(tactic: &Tactic<'ctx>) -> Solver<'ctx> {
+        Solver {
+            ctx: tactic.ctx,
+            z3_slv: unsafe {
+                let s = Z3_mk_solver_from_tactic(tactic.ctx.z3_ctx, tactic.z3_tactic);
+                Z3_solver_inc_ref(tactic.ctx.z3_ctx, s);
+                s
+            },
+        }
+    }
+
+    /// Create a new solver and load the SMT-LIB2 script `source` into it.
+    ///
+    /// # See also:
+    ///
+    /// - [`Solver::load_string()`](#method.load_string)
+    pub fn from_string(ctx: &'ctx Context, source: &str) -> Result<Solver<'ctx>, Z3Error> {
+        let solver = Solver::new(ctx);
+        solver.load_string(source)?;
+        Ok(solver)
+    }
+
+    /// Load the declarations and assertions of the SMT-LIB2 script `source`
+    /// into the solver, reporting an error if it cannot be parsed.
+    ///
+    /// Each script must declare the symbols it uses; declarations from
+    /// previously loaded scripts are not visible.
+    ///
+    /// # Examples
+    /// ```
+    /// # use z3::{Config, Context, SatResult, Solver};
+    /// # let cfg = Config::new();
+    /// # let ctx = Context::new(&cfg);
+    /// let solver = Solver::new(&ctx);
+    /// solver
+    ///     .load_string("(declare-const x Int) (assert (> x 2)) (assert (< x 2))")
+    ///     .unwrap();
+    /// assert_eq!(solver.check(), SatResult::Unsat);
+    ///
+    /// assert!(solver.load_string("(assert (> y 2))").is_err());
+    /// ```
+    pub fn load_string(&self, source: &str) -> Result<(), Z3Error> {
+        let source = c_string(source, Z3_PARSER_ERROR)?;
+        self.ctx.reset_error();
+        unsafe { Z3_solver_from_string(self.ctx.z3_ctx, self.z3_slv, source.as_ptr()) };
+        self.ctx.check_error()
+    }
+
+    /// Load the declarations and assertions of an SMT-LIB2 file into the
+    /// solver, reporting an error if it cannot be read or parsed.
+    pub fn load_file<P: AsRef<Path>>(&self, path: P) -> Result<(), Z3Error> {
+        let path = c_path(path.as_ref())?;
+        self.ctx.reset_error();
+        unsafe { Z3_solver_from_file(self.ctx.z3_ctx, self.z3_slv, path.as_ptr()) };
+        self.ctx.check_error()
+    }
+
     pub fn translate<'dest_ctx>(&self, dest: &'dest_ctx Context) -> Solver<'dest_ctx> {
         Solver {
             ctx: dest,
@@ -76,8 +167,20 @@ impl<'ctx> Solver<'ctx> {
     /// # See also:
     ///
     /// - [`Solver::assert_and_track()`](#method.assert_and_track)
+    /// - [`Solver::try_assert()`](#method.try_assert)
     pub fn assert(&self, ast: &Ast<'ctx>) {
+        self.try_assert(ast).unwrap()
+    }
+
+    /// Assert a constraint into the solver, reporting an error if `ast`
+    /// is not a Boolean expression.
+    ///
+    /// # See also:
+    ///
+    /// - [`Solver::assert()`](#method.assert)
+    pub fn try_assert(&self, ast: &Ast<'ctx>) -> Result<(), Z3Error> {
         unsafe { Z3_solver_assert(self.ctx.z3_ctx, self.z3_slv, ast.z3_ast) };
+        self.ctx.check_error()
     }
 
     /// Assert a constraint `a` into the solver, and track it (in the
@@ -98,8 +201,17 @@ impl<'ctx> Solver<'ctx> {
     /// # See also:
     ///
     /// - [`Solver::assert()`](#method.assert)
+    /// - [`Solver::try_assert_and_track()`](#method.try_assert_and_track)
     pub fn assert_and_track(&self, ast: &Ast<'ctx>, p: &Ast<'ctx>) {
-        unsafe { Z3_solver_assert(self.ctx.z3_ctx, self.z3_slv, ast.z3_ast) };
+        self.try_assert_and_track(ast, p).unwrap()
+    }
+
+    /// Assert and track a constraint, reporting an error if the
+    /// preconditions of
+    /// [`Solver::assert_and_track()`](#method.assert_and_track) are violated.
+    pub fn try_assert_and_track(&self, ast: &Ast<'ctx>, p: &Ast<'ctx>) -> Result<(), Z3Error> {
+        unsafe { Z3_solver_assert_and_track(self.ctx.z3_ctx, self.z3_slv, ast.z3_ast, p.z3_ast) };
+        self.ctx.check_error()
     }
 
     /// Remove all assertions from the solver.
@@ -130,11 +242,53 @@ impl<'ctx> Solver<'ctx> {
     ///
     /// [model construction is enabled]: struct.Config.html#method.set_model_generation
     /// [proof generation was enabled]: struct.Config.html#method.set_proof_generation
-    pub fn check(&self) -> Option<bool> {
+    pub fn check(&self) -> SatResult {
         match unsafe { Z3_solver_check(self.ctx.z3_ctx, self.z3_slv) } {
-            Z3_L_FALSE => Some(false),
-            Z3_L_UNDEF => None,
-            Z3_L_TRUE => Some(true),
+            Z3_L_FALSE => SatResult::Unsat,
+            Z3_L_UNDEF => SatResult::Unknown,
+            Z3_L_TRUE => SatResult::Sat,
+        }
+    }
+
+    /// Enumerate the models of the assertions that differ in the values of
+    /// `vars`, yielding one model for each distinct assignment to `vars`.
+    ///
+    /// After each model, the iterator asserts that its assignment to
+    /// `vars` may not occur again. These assertions are made in a new
+    /// scope that is popped when the iterator is dropped, which restores
+    /// the solver to its previous state. If `vars` is empty, at most one
+    /// model is yielded.
+    ///
+    /// The iteration also stops if [`Solver::check()`](#method.check)
+    /// returns [`SatResult::Unknown`](enum.SatResult.html#variant.Unknown);
+    /// use [`SolverModels::result()`](struct.SolverModels.html#method.result)
+    /// to tell this apart from having enumerated all models.
+    ///
+    /// ```
+    /// # use z3::{Ast, Config, Context, Solver, Sort, Symbol};
+    /// let cfg = Config::new();
+    /// let ctx = Context::new(&cfg);
+    /// let x = Ast::new_const(&Symbol::from_string(&ctx, "x"), &Sort::int(&ctx));
+    /// let solver = Solver::new(&ctx);
+    /// solver.assert(&x.ge(&Ast::from_i64(&ctx, 1)));
+    /// solver.assert(&x.le(&Ast::from_i64(&ctx, 3)));
+    ///
+    /// let mut values: Vec<i64> = solver
+    ///     .iter_models(&[&x])
+    ///     .map(|model| model.get_i64(&x).unwrap())
+    ///     .collect();
+    /// values.sort();
+    /// assert_eq!(values, vec![1, 2, 3]);
+    /// ```
+    pub fn iter_models<'s>(&'s self, vars: &[&Ast<'ctx>]) -> SolverModels<'s, 'ctx> {
+        let scopes = self.get_num_scopes();
+        self.push();
+        SolverModels {
+            solver: self,
+            scopes,
+            vars: vars.iter().map(|v| (*v).clone()).collect(),
+            limit: None,
+            result: None,
         }
     }
 
@@ -149,12 +303,217 @@ impl<'ctx> Solver<'ctx> {
     /// # See also:
     ///
     /// - [`Solver::check()`](#method.check)
-    pub fn check_assumptions(&self, assumptions: &[Ast<'ctx>]) -> bool {
+    pub fn check_assumptions(&self, assumptions: &[Ast<'ctx>]) -> SatResult {
         let a: Vec<Z3_ast> = assumptions.iter().map(|a| a.z3_ast).collect();
-        unsafe {
+        match unsafe {
             Z3_solver_check_assumptions(self.ctx.z3_ctx, self.z3_slv, a.len() as u32, a.as_ptr())
-                == Z3_L_TRUE
+        } {
+            Z3_L_FALSE => SatResult::Unsat,
+            Z3_L_UNDEF => SatResult::Unknown,
+            Z3_L_TRUE => SatResult::Sat,
+        }
+    }
+
+    /// Determine the consequences of the assertions and `assumptions` for
+    /// the values of `variables`.
+    ///
+    /// If the assertions and `assumptions` are satisfiable, returns
+    /// [`SatResult::Sat`](enum.SatResult.html#variant.Sat) together with
+    /// implications `(=> (and a1 .. an) (= v val))`, one for each variable
+    /// `v` that has the same value `val` in all models, where `a1 .. an`
+    /// are the assumptions it depends on, or `true` if there are none.
+    /// Boolean variables are reported as `v` or `(not v)` instead of an
+    /// equality.
+    ///
+    /// ```
+    /// # use z3::{Config, Context, SatResult, Solver};
+    /// # let cfg = Config::new();
+    /// # let ctx = Context::new(&cfg);
+    /// let a = ctx.named_bool_const("a");
+    /// let b = ctx.named_bool_const("b");
+    /// let c = ctx.named_bool_const("c");
+    /// let solver = Solver::new(&ctx);
+    /// solver.assert(&a.implies(&b));
+    ///
+    /// let (result, consequences) = solver.get_consequences(&[a.clone()], &[b.clone(), c.clone()]);
+    /// assert_eq!(result, SatResult::Sat);
+    /// assert_eq!(consequences, [a.implies(&b)]);
+    /// ```
+    pub fn get_consequences(
+        &self,
+        assumptions: &[Ast<'ctx>],
+        variables: &[Ast<'ctx>],
+    ) -> (SatResult, Vec<Ast<'ctx>>) {
+        self.try_get_consequences(assumptions, variables).unwrap()
+    }
+
+    /// Like [`Solver::get_consequences()`](#method.get_consequences), but
+    /// reports an error instead of panicking if Z3 fails to compute the
+    /// consequences.
+    pub fn try_get_consequences(
+        &self,
+        assumptions: &[Ast<'ctx>],
+        variables: &[Ast<'ctx>],
+    ) -> Result<(SatResult, Vec<Ast<'ctx>>), Z3Error> {
+        let a = AstVector::new(self.ctx);
+        for assumption in assumptions {
+            a.push(assumption);
+        }
+        let v = AstVector::new(self.ctx);
+        for variable in variables {
+            v.push(variable);
+        }
+        let consequences = AstVector::new(self.ctx);
+        let result = unsafe {
+            Z3_solver_get_consequences(
+                self.ctx.z3_ctx,
+                self.z3_slv,
+                a.z3_ast_vector,
+                v.z3_ast_vector,
+                consequences.z3_ast_vector,
+            )
+        };
+        self.ctx.check_error()?;
+        let result = match result {
+            Z3_L_FALSE => SatResult::Unsat,
+            Z3_L_UNDEF => SatResult::Unknown,
+            Z3_L_TRUE => SatResult::Sat,
+        };
+        Ok((result, consequences.to_vec()))
+    }
+
+    /// Check the assertions of this solver with several parameter
+    /// configurations in parallel, and return the first result that is
+    /// not [`SatResult::Unknown`](enum.SatResult.html#variant.Unknown).
+    ///
+    /// For every configuration, the solver is translated into a fresh
+    /// context created from `cfg` and checked on its own thread. The
+    /// configuration is called with that context and empty parameters to
+    /// fill in, and the parameters are set on the translated solver before
+    /// the check. As soon as one check is conclusive the others are
+    /// interrupted. If no check is conclusive, the result is `Unknown`.
+    ///
+    /// Panics if the parameters of a configuration are invalid, and
+    /// propagates a panic of a configuration once the other checks have
+    /// been interrupted.
+    ///
+    /// # Examples
+    /// ```
+    /// # use z3::{Config, Context, Params, SatResult, Solver, Symbol};
+    /// let cfg = Config::new();
+    /// let ctx = Context::new(&cfg);
+    /// let solver = Solver::new(&ctx);
+    /// let x = ctx.named_int_const("x");
+    /// solver.assert(&x.mul(&[&x])._eq(&ctx.from_i64(49)));
+    ///
+    /// let default = |_: &Context, _: &Params| {};
+    /// let seeded = |ctx: &Context, params: &Params| {
+    ///     params.set_uint(&Symbol::from_string(ctx, "random_seed"), 42);
+    /// };
+    /// assert_eq!(
+    ///     solver.check_portfolio(&cfg, &[&default, &seeded]),
+    ///     SatResult::Sat
+    /// );
+    /// ```
+    pub fn check_portfolio(&self, cfg: &Config, configs: &[&PortfolioConfig]) -> SatResult {
+        self.try_check_portfolio(cfg, configs).unwrap()
+    }
+
+    /// Like [`Solver::check_portfolio()`](#method.check_portfolio), but
+    /// reports an error instead of panicking if the parameters of a
+    /// configuration are invalid.
+    pub fn try_check_portfolio(
+        &self,
+        cfg: &Config,
+        configs: &[&PortfolioConfig],
+    ) -> Result<SatResult, Z3Error> {
+        // The translated solvers are only used by the thread they are moved
+        // to, and their contexts by nothing else but `ContextHandle`s.
+        struct Worker<'a>(Solver<'a>);
+        unsafe impl<'a> Send for Worker<'a> {}
+
+        let contexts: Vec<Context> = configs.iter().map(|_| Context::new(cfg)).collect();
+        let handles: Vec<ContextHandle> = contexts.iter().map(|c| c.handle()).collect();
+        let workers: Vec<Worker> = contexts.iter().map(|c| Worker(self.translate(c))).collect();
+
+        // Every worker sends the result of its check, or `None` if it
+        // panicked. The channel disconnects once all workers are done.
+        let (sender, receiver) = mpsc::channel();
+        thread::scope(|s| {
+            for (worker, config) in workers.into_iter().zip(configs) {
+                let sender = sender.clone();
+                s.spawn(move || {
+                    let solver = worker.0;
+                    let outcome = panic::catch_unwind(AssertUnwindSafe(|| {
+                        let params = Params::new(solver.ctx);
+                        config(solver.ctx, &params);
+                        solver.try_set_params(&params)?;
+                        Ok(solver.check())
+                    }));
+                    match outcome {
+                        Ok(result) => {
+                            let _ = sender.send(Some(result));
+                        }
+                        Err(payload) => {
+                            let _ = sender.send(None);
+                            panic::resume_unwind(payload);
+                        }
+                    }
+                });
+            }
+            drop(sender);
+
+            let mut result = Ok(SatResult::Unknown);
+            let mut stop = false;
+            loop {
+                // An interrupt is lost if it arrives before a check has
+                // started, so once the outcome is known keep interrupting
+                // until every worker is done.
+                let message = if stop {
+                    for h in &handles {
+                        h.interrupt();
+                    }
+                    receiver.recv_timeout(Duration::from_millis(10))
+                } else {
+                    receiver.recv().map_err(RecvTimeoutError::from)
+                };
+                match message {
+                    Ok(Some(Ok(SatResult::Unknown))) | Err(RecvTimeoutError::Timeout) => {}
+                    Ok(Some(r)) => {
+                        if !stop {
+                            result = r;
+                            stop = true;
+                        }
+                    }
+                    Ok(None) => stop = true,
+                    Err(RecvTimeoutError::Disconnected) => break,
+                }
+            }
+            result
+        })
+    }
+
+    /// Retrieve the unsat core for the last
+    /// [`Solver::check_assumptions()`](#method.check_assumptions).
+    ///
+    /// The unsat core is a subset of the assumptions and of the Boolean
+    /// constants used to track assertions with
+    /// [`Solver::assert_and_track()`](#method.assert_and_track).
+    ///
+    /// By default, the unsat core will not be minimized. Generation of a minimized
+    /// unsat core can be enabled via the `"sat.core.minimize"` and
+    /// `"smt.core.minimize"` settings for SAT and SMT cores respectively.
+    ///
+    /// # See also:
+    ///
+    /// - [`Solver::assert_and_track()`](#method.assert_and_track)
+    /// - [`Solver::check_assumptions()`](#method.check_assumptions)
+    pub fn get_unsat_core(&self) -> Vec<Ast<'ctx>> {
+        let z3_unsat_core = unsafe { Z3_solver_get_unsat_core(self.ctx.z3_ctx, self.z3_slv) };
+        if z3_unsat_core.is_null() {
+            return vec![];
         }
+        unsafe { AstVector::from_raw(self.ctx, z3_unsat_core) }.to_vec()
     }
 
     /// Create a backtracking point.
@@ -174,35 +533,236 @@ impl<'ctx> Solver<'ctx> {
     ///
     /// - [`Solver::push()`](#method.push)
     pub fn pop(&self, n: u32) {
+        self.try_pop(n).unwrap()
+    }
+
+    /// Backtrack `n` backtracking points, reporting an error if there
+    /// are fewer than `n` of them.
+    pub fn try_pop(&self, n: u32) -> Result<(), Z3Error> {
         unsafe { Z3_solver_pop(self.ctx.z3_ctx, self.z3_slv, n) };
+        self.ctx.check_error()
+    }
+
+    /// Return the number of backtracking points.
+    ///
+    /// # See also:
+    ///
+    /// - [`Solver::push()`](#method.push)
+    /// - [`Solver::pop()`](#method.pop)
+    pub fn get_num_scopes(&self) -> u32 {
+        unsafe { Z3_solver_get_num_scopes(self.ctx.z3_ctx, self.z3_slv) }
+    }
+
+    /// Return the assertions of this solver, in all scopes.
+    ///
+    /// ```
+    /// # use z3::{Config, Context, Solver};
+    /// # let cfg = Config::new();
+    /// # let ctx = Context::new(&cfg);
+    /// let x = ctx.named_int_const("x");
+    /// let solver = Solver::new(&ctx);
+    /// solver.assert(&x.gt(&ctx.from_i64(0)));
+    /// solver.push();
+    /// solver.assert(&x.lt(&ctx.from_i64(2)));
+    /// assert_eq!(solver.get_num_scopes(), 1);
+    /// assert_eq!(solver.get_assertions().len(), 2);
+    ///
+    /// solver.pop(1);
+    /// assert_eq!(solver.get_assertions(), [x.gt(&ctx.from_i64(0))]);
+    /// ```
+    pub fn get_assertions(&self) -> Vec<Ast<'ctx>> {
+        unsafe {
+            AstVector::from_raw(
+                self.ctx,
+                Z3_solver_get_assertions(self.ctx.z3_ctx, self.z3_slv),
+            )
+        }
+        .to_vec()
+    }
+
+    /// Return the literals that the last check derived to be true
+    /// independently of any decision.
+    pub fn get_units(&self) -> Vec<Ast<'ctx>> {
+        unsafe { AstVector::from_raw(self.ctx, Z3_solver_get_units(self.ctx.z3_ctx, self.z3_slv)) }
+            .to_vec()
+    }
+
+    /// Return the assertions of this solver that are not unit literals,
+    /// as simplified by the last check.
+    pub fn get_non_units(&self) -> Vec<Ast<'ctx>> {
+        unsafe {
+            AstVector::from_raw(
+                self.ctx,
+                Z3_solver_get_non_units(self.ctx.z3_ctx, self.z3_slv),
+            )
+        }
+        .to_vec()
+    }
+
+    /// Return the literals assigned by the last check, in the order in
+    /// which they were assigned.
+    ///
+    /// Panics if the solver does not keep a trail; see
+    /// [`Solver::try_get_trail()`](#method.try_get_trail).
+    pub fn get_trail(&self) -> Vec<Ast<'ctx>> {
+        self.try_get_trail().unwrap()
+    }
+
+    /// Like [`Solver::get_trail()`](#method.get_trail), but reports an
+    /// error if the solver does not keep a trail. Only the SAT solver
+    /// keeps one, which is used by solvers for the `QF_FD` logic; see
+    /// [`Solver::new_for_logic()`](#method.new_for_logic).
+    pub fn try_get_trail(&self) -> Result<Vec<Ast<'ctx>>, Z3Error> {
+        let trail = unsafe { Z3_solver_get_trail(self.ctx.z3_ctx, self.z3_slv) };
+        self.ctx.check_error()?;
+        let trail = unsafe { AstVector::from_raw(self.ctx, trail) };
+        // Internal variables without a term are reported as null; skip them.
+        Ok((0..trail.len() as u32)
+            .map(|i| unsafe { Z3_ast_vector_get(self.ctx.z3_ctx, trail.z3_ast_vector, i) })
+            .filter(|ast| !ast.is_null())
+            .map(|ast| Ast::new(self.ctx, ast))
+            .collect())
     }
 
     /// Retrieve the model for the last [`Solver::check()`](#method.check)
     /// or [`Solver::check_assumptions()`](#method.check_assumptions)
     ///
-    /// The error handler is invoked if a model is not available because
-    /// the commands above were not invoked for the given solver, or if
-    /// the result was `Z3_L_FALSE`.
+    /// Panics if a model is not available because the commands above
+    /// were not invoked for the given solver, or if the result was
+    /// [`SatResult::Unsat`](enum.SatResult.html#variant.Unsat).
+    ///
+    /// # See also:
+    ///
+    /// - [`Solver::try_get_model()`](#method.try_get_model)
     pub fn get_model(&self) -> Model<'ctx> {
         Model::of_solver(self)
     }
 
+    /// Retrieve the model for the last [`Solver::check()`](#method.check)
+    /// or [`Solver::check_assumptions()`](#method.check_assumptions),
+    /// reporting an error if no model is available.
+    pub fn try_get_model(&self) -> Result<Model<'ctx>, Z3Error> {
+        Model::try_of_solver(self)
+    }
+
     /// Retrieve the proof for the last [`Solver::check()`](#method.check)
     /// or [`Solver::check_assumptions()`](#method.check_assumptions)
     ///
-    /// The error handler is invoked if [proof generation is not enabled],
+    /// Panics if [proof generation is not enabled],
     /// or if the commands above were not invoked for the given solver,
-    /// or if the result was different from `Z3_L_FALSE`.
+    /// or if the result was different from
+    /// [`SatResult::Unsat`](enum.SatResult.html#variant.Unsat).
     ///
     /// # See also:
     ///
     /// - [`Config::set_proof_generation()`](struct.Config.html#method.set_proof_generation)
+    /// - [`Solver::try_get_proof()`](#method.try_get_proof)
     ///
     /// [proof generation is not enabled]: struct.Config.html#method.set_proof_generation
     pub fn get_proof(&self) -> Ast<'ctx> {
-        Ast::new(self.ctx, unsafe {
-            Z3_solver_get_proof(self.ctx.z3_ctx, self.z3_slv)
-        })
+        self.try_get_proof().unwrap()
+    }
+
+    /// Retrieve the proof for the last [`Solver::check()`](#method.check)
+    /// or [`Solver::check_assumptions()`](#method.check_assumptions),
+    /// reporting an error if no proof is available.
+    pub fn try_get_proof(&self) -> Result<Ast<'ctx>, Z3Error> {
+        let proof = unsafe { Z3_solver_get_proof(self.ctx.z3_ctx, self.z3_slv) };
+        self.ctx.check_error()?;
+        Ok(Ast::new(self.ctx, proof))
+    }
+
+    /// Return a brief justification for an "unknown" result (i.e.,
+    /// [`SatResult::Unknown`](enum.SatResult.html#variant.Unknown)) for
+    /// the last [`Solver::check()`](#method.check) or
+    /// [`Solver::check_assumptions()`](#method.check_assumptions).
+    pub fn get_reason_unknown(&self) -> Option<String> {
+        let p = unsafe { Z3_solver_get_reason_unknown(self.ctx.z3_ctx, self.z3_slv) };
+        if p.is_null() {
+            return None;
+        }
+        unsafe { CStr::from_ptr(p) }
+            .to_str()
+            .ok()
+            .map(|s| s.to_string())
+    }
+
+    /// Return a standalone SMT-LIB2 script with the declarations and
+    /// assertions of this solver, followed by `(check-sat)`.
+    ///
+    /// The script can be replayed with the `z3` command line tool, e.g. to
+    /// report a problem upstream.
+    ///
+    /// # Examples
+    /// ```
+    /// # use z3::{Config, Context, SatResult, Solver};
+    /// # let cfg = Config::new();
+    /// # let ctx = Context::new(&cfg);
+    /// let solver = Solver::new(&ctx);
+    /// let x = ctx.named_int_const("x");
+    /// solver.assert(&x.gt(&ctx.from_i64(2)));
+    ///
+    /// let smt2 = solver.to_smt2();
+    /// assert!(smt2.contains("(declare-fun x () Int)"));
+    /// assert!(smt2.contains("(check-sat)"));
+    ///
+    /// let replayed = Solver::from_string(&ctx, &smt2).unwrap();
+    /// assert_eq!(replayed.check(), SatResult::Sat);
+    /// ```
+    pub fn to_smt2(&self) -> String {
+        let assertions = self.get_assertions();
+        // All but the last assertion are passed as assumptions, which are
+        // printed as assertions as well.
+        let true_ = Ast::from_bool(self.ctx, true);
+        let (formula, assumptions) = match assertions.split_last() {
+            Some((last, rest)) => (last, rest),
+            None => (&true_, &[][..]),
+        };
+        let assumptions: Vec<Z3_ast> = assumptions.iter().map(|a| a.z3_ast).collect();
+        let empty = CString::new("").unwrap();
+        let status = CString::new("unknown").unwrap();
+        unsafe {
+            let s = Z3_benchmark_to_smtlib_string(
+                self.ctx.z3_ctx,
+                empty.as_ptr(),
+                empty.as_ptr(),
+                status.as_ptr(),
+                empty.as_ptr(),
+                assumptions.len() as u32,
+                assumptions.as_ptr(),
+                formula.z3_ast,
+            );
+            CStr::from_ptr(s).to_string_lossy().into_owned()
+        }
+    }
+
+    /// Set the parameters of the solver.
+    ///
+    /// Panics if a parameter is unknown or has the wrong type; see
+    /// [`Solver::try_set_params()`](#method.try_set_params).
+    ///
+    /// # See also:
+    ///
+    /// - [`SolverParams`](struct.SolverParams.html)
+    pub fn set_params(&self, params: &Params<'ctx>) {
+        self.try_set_params(params).unwrap()
+    }
+
+    /// Set the parameters of the solver, reporting an error if a
+    /// parameter is not described by
+    /// [`Solver::get_param_descrs()`](#method.get_param_descrs) or has the
+    /// wrong type.
+    pub fn try_set_params(&self, params: &Params<'ctx>) -> Result<(), Z3Error> {
+        params.try_validate(&self.get_param_descrs())?;
+        unsafe { Z3_solver_set_params(self.ctx.z3_ctx, self.z3_slv, params.z3_params) };
+        self.ctx.check_error()
+    }
+
+    /// Return statistics about the last
+    /// [`Solver::check()`](#method.check), such as the number of
+    /// conflicts and decisions.
+    pub fn get_statistics(&self) -> Stats<'ctx> {
+        Stats::with_solver(self)
     }
 
     pub fn get_help(&self) -> String {
@@ -222,6 +782,72 @@ impl<'ctx> Solver<'ctx> {
     }
 }
 
+impl<'s, 'ctx> SolverModels<'s, 'ctx> {
+    /// Stop after yielding at most `limit` models.
+    pub fn with_limit(mut self, limit: usize) -> SolverModels<'s, 'ctx> {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// Return why the iteration ended: [`SatResult::Unsat`](enum.SatResult.html#variant.Unsat)
+    /// if all models have been enumerated, and
+    /// [`SatResult::Unknown`](enum.SatResult.html#variant.Unknown) if the
+    /// solver gave up, e.g. because of a timeout or resource limit.
+    ///
+    /// Returns `None` while the iteration has not ended, or if it ended
+    /// because of the limit set with
+    /// [`SolverModels::with_limit()`](#method.with_limit).
+    pub fn result(&self) -> Option<SatResult> {
+        self.result
+    }
+}
+
+impl<'s, 'ctx> Iterator for SolverModels<'s, 'ctx> {
+    type Item = Model<'ctx>;
+
+    fn next(&mut self) -> Option<Model<'ctx>> {
+        if self.result.is_some() || self.limit == Some(0) {
+            return None;
+        }
+        match self.solver.check() {
+            SatResult::Sat => {}
+            result => {
+                self.result = Some(result);
+                return None;
+            }
+        }
+        let model = self.solver.get_model();
+        let diffs: Vec<Ast<'ctx>> = self
+            .vars
+            .iter()
+            .map(|v| v._eq(&model.eval(v).unwrap()).not())
+            .collect();
+        match diffs.split_first() {
+            Some((first, rest)) => {
+                let rest: Vec<&Ast<'ctx>> = rest.iter().collect();
+                self.solver.assert(&first.or(&rest));
+            }
+            // There is only one assignment to no variables.
+            None => self.result = Some(SatResult::Unsat),
+        }
+        if let Some(ref mut limit) = self.limit {
+            *limit -= 1;
+        }
+        Some(model)
+    }
+}
+
+impl<'s, 'ctx> Drop for SolverModels<'s, 'ctx> {
+    fn drop(&mut self) {
+        // Pop the scope pushed by `iter_models`, and any the caller left
+        // open. There is nothing to pop if the caller already popped it.
+        let n = self.solver.get_num_scopes().saturating_sub(self.scopes);
+        if n > 0 {
+            let _ = self.solver.try_pop(n);
+        }
+    }
+}
+
 impl<'ctx> fmt::Display for Solver<'ctx> {
     fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
         let p =
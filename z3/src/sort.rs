@@ -7,13 +7,25 @@ use Context;
 use FuncDecl;
 use Sort;
 use Symbol;
+use Z3Error;
 
 impl<'ctx> Sort<'ctx> {
+    pub(crate) unsafe fn from_raw(ctx: &'ctx Context, z3_sort: Z3_sort) -> Sort<'ctx> {
+        Z3_inc_ref(ctx.z3_ctx, Z3_sort_to_ast(ctx.z3_ctx, z3_sort));
+        Sort { ctx, z3_sort }
+    }
+
+    /// Wrap the result of a Z3 API call, or report the error of that call.
+    pub(crate) unsafe fn try_from_raw(
+        ctx: &'ctx Context,
+        z3_sort: Z3_sort,
+    ) -> Result<Sort<'ctx>, Z3Error> {
+        ctx.check_error()?;
+        Ok(Sort::from_raw(ctx, z3_sort))
+    }
+
     pub fn uninterpreted(ctx: &'ctx Context, sym: &Symbol<'ctx>) -> Sort<'ctx> {
-        Sort {
-            ctx,
-            z3_sort: unsafe { Z3_mk_uninterpreted_sort(ctx.z3_ctx, sym.z3_sym) },
-        }
+        unsafe { Sort::from_raw(ctx, Z3_mk_uninterpreted_sort(ctx.z3_ctx, sym.z3_sym)) }
     }
 
     pub fn bool(ctx: &Context) -> Sort {
@@ -60,6 +72,52 @@ impl<'ctx> Sort<'ctx> {
         }
     }
 
+    /// Create an IEEE 754 floating-point sort with `ebits` exponent bits and
+    /// `sbits` significand bits, including the hidden bit.
+    ///
+    /// Panics unless `ebits` is at least 2 and `sbits` at least 3.
+    pub fn float(ctx: &'ctx Context, ebits: u32, sbits: u32) -> Sort<'ctx> {
+        Sort::try_float(ctx, ebits, sbits).unwrap()
+    }
+
+    /// Like [`Sort::float()`](#method.float), but reports an error instead
+    /// of panicking if the number of bits is invalid.
+    pub fn try_float(ctx: &'ctx Context, ebits: u32, sbits: u32) -> Result<Sort<'ctx>, Z3Error> {
+        unsafe { Sort::try_from_raw(ctx, Z3_mk_fpa_sort(ctx.z3_ctx, ebits, sbits)) }
+    }
+
+    /// Create the sort of single precision floating-point numbers, like
+    /// `f32`.
+    pub fn float32(ctx: &'ctx Context) -> Sort<'ctx> {
+        unsafe { Sort::from_raw(ctx, Z3_mk_fpa_sort_32(ctx.z3_ctx)) }
+    }
+
+    /// Create the sort of double precision floating-point numbers, like
+    /// `f64`.
+    pub fn float64(ctx: &'ctx Context) -> Sort<'ctx> {
+        unsafe { Sort::from_raw(ctx, Z3_mk_fpa_sort_64(ctx.z3_ctx)) }
+    }
+
+    /// Create the sort of floating-point rounding modes.
+    pub fn rounding_mode(ctx: &'ctx Context) -> Sort<'ctx> {
+        unsafe { Sort::from_raw(ctx, Z3_mk_fpa_rounding_mode_sort(ctx.z3_ctx)) }
+    }
+
+    /// Create the sort of strings, i.e. of sequences of characters.
+    pub fn string(ctx: &'ctx Context) -> Sort<'ctx> {
+        unsafe { Sort::from_raw(ctx, Z3_mk_string_sort(ctx.z3_ctx)) }
+    }
+
+    /// Create the sort of sequences whose elements have sort `elem`.
+    pub fn seq(ctx: &'ctx Context, elem: &Sort<'ctx>) -> Sort<'ctx> {
+        unsafe { Sort::try_from_raw(ctx, Z3_mk_seq_sort(ctx.z3_ctx, elem.z3_sort)) }.unwrap()
+    }
+
+    /// Create the sort of regular expressions over the sequence sort `seq`.
+    pub fn re(ctx: &'ctx Context, seq: &Sort<'ctx>) -> Sort<'ctx> {
+        unsafe { Sort::try_from_raw(ctx, Z3_mk_re_sort(ctx.z3_ctx, seq.z3_sort)) }.unwrap()
+    }
+
     pub fn array(ctx: &'ctx Context, domain: &Sort<'ctx>, range: &Sort<'ctx>) -> Sort<'ctx> {
         Sort {
             ctx,
@@ -141,6 +199,102 @@ impl<'ctx> Sort<'ctx> {
         (sort, enum_consts, enum_testers)
     }
 
+    /// Return the kind of this sort, e.g. `Z3_BOOL_SORT` or `Z3_BV_SORT`.
+    pub fn kind(&self) -> Z3_sort_kind {
+        unsafe { Z3_get_sort_kind(self.ctx.z3_ctx, self.z3_sort) }
+    }
+
+    /// Return the width of a bit-vector sort, or `None` if this is not a
+    /// bit-vector sort.
+    pub fn bv_size(&self) -> Option<u32> {
+        if self.kind() == Z3_BV_SORT {
+            Some(unsafe { Z3_get_bv_sort_size(self.ctx.z3_ctx, self.z3_sort) })
+        } else {
+            None
+        }
+    }
+
+    /// Return the number of exponent bits of a floating-point sort, or
+    /// `None` if this is not a floating-point sort.
+    pub fn float_ebits(&self) -> Option<u32> {
+        if self.kind() == Z3_FLOATING_POINT_SORT {
+            Some(unsafe { Z3_fpa_get_ebits(self.ctx.z3_ctx, self.z3_sort) })
+        } else {
+            None
+        }
+    }
+
+    /// Return the number of significand bits of a floating-point sort,
+    /// including the hidden bit, or `None` if this is not a floating-point
+    /// sort.
+    pub fn float_sbits(&self) -> Option<u32> {
+        if self.kind() == Z3_FLOATING_POINT_SORT {
+            Some(unsafe { Z3_fpa_get_sbits(self.ctx.z3_ctx, self.z3_sort) })
+        } else {
+            None
+        }
+    }
+
+    /// Return the element sort of a sequence sort, or `None` if this is not
+    /// a sequence sort.
+    pub fn seq_element(&self) -> Option<Sort<'ctx>> {
+        if self.kind() == Z3_SEQ_SORT {
+            Some(unsafe {
+                Sort::from_raw(
+                    self.ctx,
+                    Z3_get_seq_sort_basis(self.ctx.z3_ctx, self.z3_sort),
+                )
+            })
+        } else {
+            None
+        }
+    }
+
+    /// Return the sequence sort matched by a regular expression sort, or
+    /// `None` if this is not a regular expression sort.
+    pub fn re_seq(&self) -> Option<Sort<'ctx>> {
+        if self.kind() == Z3_RE_SORT {
+            Some(unsafe {
+                Sort::from_raw(
+                    self.ctx,
+                    Z3_get_re_sort_basis(self.ctx.z3_ctx, self.z3_sort),
+                )
+            })
+        } else {
+            None
+        }
+    }
+
+    /// Return the domain of an array sort, or `None` if this is not an
+    /// array sort.
+    pub fn array_domain(&self) -> Option<Sort<'ctx>> {
+        if self.kind() == Z3_ARRAY_SORT {
+            Some(unsafe {
+                Sort::from_raw(
+                    self.ctx,
+                    Z3_get_array_sort_domain(self.ctx.z3_ctx, self.z3_sort),
+                )
+            })
+        } else {
+            None
+        }
+    }
+
+    /// Return the range of an array sort, or `None` if this is not an
+    /// array sort.
+    pub fn array_range(&self) -> Option<Sort<'ctx>> {
+        if self.kind() == Z3_ARRAY_SORT {
+            Some(unsafe {
+                Sort::from_raw(
+                    self.ctx,
+                    Z3_get_array_sort_range(self.ctx.z3_ctx, self.z3_sort),
+                )
+            })
+        } else {
+            None
+        }
+    }
+
     /// Converts an unsigned integer to an `Ast` of the given `Sort`
     ///
     /// `self` must be an integer, bit-vector, or finite-domain sort.
@@ -162,7 +316,7 @@ impl<'ctx> Sort<'ctx> {
     /// let solver = Solver::new(&ctx);
     /// solver.assert(&x.bvadd(&bv_sort.from_u64(1))
     ///     ._eq(&bv_sort.from_u64(0)));
-    /// assert!(solver.check());
+    /// assert_eq!(solver.check(), SatResult::Sat);
     ///
     /// let model = solver.get_model();
     /// assert!(model.eval(&x).and_then(|i| i.as_u64()).unwrap_or(0) == 0xFF);
@@ -193,7 +347,7 @@ impl<'ctx> Sort<'ctx> {
     /// solver.assert(&bv_sort.from_i64(-5)
     ///     .bvmul(&bv_sort.from_i64(-100))
     ///     ._eq(&bv_sort.from_i64((-5 * -100) % 256)));
-    /// assert!(solver.check());
+    /// assert_eq!(solver.check(), SatResult::Sat);
     /// ```
     pub fn from_i64(&self, i: i64) -> Ast<'ctx> {
         Ast::new(self.ctx, unsafe {
@@ -216,6 +370,12 @@ impl<'ctx> fmt::Display for Sort<'ctx> {
     }
 }
 
+impl<'ctx> fmt::Debug for Sort<'ctx> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        <Self as fmt::Display>::fmt(self, f)
+    }
+}
+
 impl<'ctx> PartialEq<Sort<'ctx>> for Sort<'ctx> {
     fn eq(&self, other: &Sort<'ctx>) -> bool {
         unsafe { Z3_is_eq_sort(self.ctx.z3_ctx, self.z3_sort, other.z3_sort) }
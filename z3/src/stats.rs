@@ -10,6 +10,98 @@ impl<'ctx> Stats<'ctx> {
             Self { ctx, z3_stats }
         }
     }
+
+    pub fn with_optimize(optimize: &Optimize<'ctx>) -> Self {
+        let ctx = optimize.ctx;
+        unsafe {
+            let z3_stats = Z3_optimize_get_statistics(ctx.z3_ctx, optimize.z3_opt);
+            Z3_stats_inc_ref(ctx.z3_ctx, z3_stats);
+
+            Self { ctx, z3_stats }
+        }
+    }
+
+    pub fn with_fixedpoint(fixedpoint: &Fixedpoint<'ctx>) -> Self {
+        let ctx = fixedpoint.ctx;
+        unsafe {
+            let z3_stats = Z3_fixedpoint_get_statistics(ctx.z3_ctx, fixedpoint.z3_fixedpoint);
+            Z3_stats_inc_ref(ctx.z3_ctx, z3_stats);
+
+            Self { ctx, z3_stats }
+        }
+    }
+}
+
+impl<'ctx> Stats<'ctx> {
+    /// Return the number of entries.
+    pub fn len(&self) -> usize {
+        unsafe { Z3_stats_size(self.ctx.z3_ctx, self.z3_stats) as usize }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Iterate over the entries as `(key, value)` pairs, e.g.
+    /// `("conflicts", StatsValue::UInt(5))`.
+    ///
+    /// ```
+    /// # use z3::{Config, Context, SatResult, Solver, StatsValue};
+    /// # let cfg = Config::new();
+    /// # let ctx = Context::new(&cfg);
+    /// let solver = Solver::new(&ctx);
+    /// let x = ctx.named_int_const("x");
+    /// solver.assert(&x.gt(&ctx.from_i64(2)));
+    /// assert_eq!(solver.check(), SatResult::Sat);
+    ///
+    /// let stats = solver.get_statistics();
+    /// for (key, value) in stats.iter() {
+    ///     match value {
+    ///         StatsValue::UInt(v) => println!("{}: {}", key, v),
+    ///         StatsValue::Double(v) => println!("{}: {:.2}", key, v),
+    ///     }
+    /// }
+    /// assert!(stats.get("memory").is_some());
+    /// ```
+    pub fn iter(&self) -> std::vec::IntoIter<(String, StatsValue)> {
+        (0..self.len() as u32)
+            .map(|i| (self.key(i), self.value(i)))
+            .collect::<Vec<_>>()
+            .into_iter()
+    }
+
+    /// Return the value of `key`, or `None` if there is no such entry.
+    pub fn get(&self, key: &str) -> Option<StatsValue> {
+        (0..self.len() as u32)
+            .find(|&i| self.key(i) == key)
+            .map(|i| self.value(i))
+    }
+
+    fn key(&self, i: u32) -> String {
+        let p = unsafe { Z3_stats_get_key(self.ctx.z3_ctx, self.z3_stats, i) };
+        unsafe { std::ffi::CStr::from_ptr(p) }
+            .to_string_lossy()
+            .into_owned()
+    }
+
+    fn value(&self, i: u32) -> StatsValue {
+        unsafe {
+            if Z3_stats_is_uint(self.ctx.z3_ctx, self.z3_stats, i) {
+                StatsValue::UInt(Z3_stats_get_uint_value(self.ctx.z3_ctx, self.z3_stats, i))
+            } else {
+                StatsValue::Double(Z3_stats_get_double_value(self.ctx.z3_ctx, self.z3_stats, i))
+            }
+        }
+    }
+}
+
+impl<'ctx> IntoIterator for &Stats<'ctx> {
+    type Item = (String, StatsValue);
+    type IntoIter = std::vec::IntoIter<(String, StatsValue)>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
 }
 
 impl<'ctx> Drop for Stats<'ctx> {
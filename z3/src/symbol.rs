@@ -1,4 +1,4 @@
-use std::ffi::CString;
+use std::ffi::{CStr, CString};
 use z3_sys::*;
 use Context;
 use Symbol;
@@ -35,3 +35,14 @@ impl<'ctx> Symbol<'ctx> {
         }
     }
 }
+
+/// Render a symbol the way Z3 prints it: string symbols verbatim, integer
+/// symbols as `k!<n>`.
+pub(crate) unsafe fn symbol_to_string(ctx: &Context, sym: Z3_symbol) -> String {
+    match Z3_get_symbol_kind(ctx.z3_ctx, sym) {
+        Z3_INT_SYMBOL => format!("k!{}", Z3_get_symbol_int(ctx.z3_ctx, sym)),
+        Z3_STRING_SYMBOL => CStr::from_ptr(Z3_get_symbol_string(ctx.z3_ctx, sym))
+            .to_string_lossy()
+            .into_owned(),
+    }
+}
@@ -0,0 +1,217 @@
+use error::c_string;
+use std::convert::TryInto;
+use std::ffi::{CStr, CString};
+use std::time::Duration;
+use z3_sys::*;
+use {ApplyResult, Context, Goal, ParamDescrs, Params, Probe, Tactic, Z3Error};
+
+impl<'ctx> Tactic<'ctx> {
+    /// Return the names of all tactics supported by Z3.
+    pub fn list_all(ctx: &'ctx Context) -> Vec<String> {
+        let n = unsafe { Z3_get_num_tactics(ctx.z3_ctx) };
+        (0..n)
+            .map(|i| unsafe {
+                CStr::from_ptr(Z3_get_tactic_name(ctx.z3_ctx, i))
+                    .to_string_lossy()
+                    .into_owned()
+            })
+            .collect()
+    }
+
+    /// Return a description of the tactic called `name`.
+    pub fn describe(ctx: &'ctx Context, name: &str) -> String {
+        let name = CString::new(name).unwrap();
+        unsafe {
+            CStr::from_ptr(Z3_tactic_get_descr(ctx.z3_ctx, name.as_ptr()))
+                .to_string_lossy()
+                .into_owned()
+        }
+    }
+
+    /// Create the tactic called `name`, e.g. `"simplify"` or `"smt"`.
+    ///
+    /// Panics if there is no such tactic; see
+    /// [`Tactic::list_all()`](#method.list_all).
+    pub fn new(ctx: &'ctx Context, name: &str) -> Tactic<'ctx> {
+        Tactic::try_new(ctx, name).unwrap()
+    }
+
+    /// Create the tactic called `name`, reporting an error if there is no
+    /// such tactic.
+    pub fn try_new(ctx: &'ctx Context, name: &str) -> Result<Tactic<'ctx>, Z3Error> {
+        let name = c_string(name, Z3_INVALID_ARG)?;
+        let t = unsafe { Z3_mk_tactic(ctx.z3_ctx, name.as_ptr()) };
+        ctx.check_error()?;
+        Ok(unsafe { Tactic::from_raw(ctx, t) })
+    }
+
+    pub(crate) unsafe fn from_raw(ctx: &'ctx Context, z3_tactic: Z3_tactic) -> Tactic<'ctx> {
+        Z3_tactic_inc_ref(ctx.z3_ctx, z3_tactic);
+        Tactic { ctx, z3_tactic }
+    }
+
+    /// Create a tactic that returns the goal unchanged.
+    pub fn skip(ctx: &'ctx Context) -> Tactic<'ctx> {
+        unsafe { Tactic::from_raw(ctx, Z3_tactic_skip(ctx.z3_ctx)) }
+    }
+
+    /// Create a tactic that always fails.
+    pub fn fail(ctx: &'ctx Context) -> Tactic<'ctx> {
+        unsafe { Tactic::from_raw(ctx, Z3_tactic_fail(ctx.z3_ctx)) }
+    }
+
+    /// Create a tactic that fails if `p` evaluates to true on the goal.
+    pub fn fail_if(p: &Probe<'ctx>) -> Tactic<'ctx> {
+        unsafe { Tactic::from_raw(p.ctx, Z3_tactic_fail_if(p.ctx.z3_ctx, p.z3_probe)) }
+    }
+
+    /// Create a tactic that applies `self` to the goal and then `t` to
+    /// every subgoal produced by `self`.
+    pub fn and_then(&self, t: &Tactic<'ctx>) -> Tactic<'ctx> {
+        unsafe {
+            Tactic::from_raw(
+                self.ctx,
+                Z3_tactic_and_then(self.ctx.z3_ctx, self.z3_tactic, t.z3_tactic),
+            )
+        }
+    }
+
+    /// Like [`Tactic::and_then()`](#method.and_then), but `t` is applied
+    /// to the subgoals in parallel.
+    pub fn par_and_then(&self, t: &Tactic<'ctx>) -> Tactic<'ctx> {
+        unsafe {
+            Tactic::from_raw(
+                self.ctx,
+                Z3_tactic_par_and_then(self.ctx.z3_ctx, self.z3_tactic, t.z3_tactic),
+            )
+        }
+    }
+
+    /// Create a tactic that applies `self` to the goal, and `t` if `self`
+    /// fails.
+    pub fn or_else(&self, t: &Tactic<'ctx>) -> Tactic<'ctx> {
+        unsafe {
+            Tactic::from_raw(
+                self.ctx,
+                Z3_tactic_or_else(self.ctx.z3_ctx, self.z3_tactic, t.z3_tactic),
+            )
+        }
+    }
+
+    /// Create a tactic that applies the given tactics in parallel and
+    /// returns the result of the first one that succeeds.
+    pub fn par_or(ctx: &'ctx Context, tactics: &[&Tactic<'ctx>]) -> Tactic<'ctx> {
+        assert!(tactics.iter().all(|t| t.ctx.z3_ctx == ctx.z3_ctx));
+
+        let tactics: Vec<_> = tactics.iter().map(|t| t.z3_tactic).collect();
+        unsafe {
+            Tactic::from_raw(
+                ctx,
+                Z3_tactic_par_or(
+                    ctx.z3_ctx,
+                    tactics.len().try_into().unwrap(),
+                    tactics.as_ptr(),
+                ),
+            )
+        }
+    }
+
+    /// Create a tactic that keeps applying `self` to the subgoals until
+    /// no subgoal is modified, at most `max` times.
+    pub fn repeat(&self, max: u32) -> Tactic<'ctx> {
+        unsafe {
+            Tactic::from_raw(
+                self.ctx,
+                Z3_tactic_repeat(self.ctx.z3_ctx, self.z3_tactic, max),
+            )
+        }
+    }
+
+    /// Create a tactic that applies `self`, and fails if it does not
+    /// terminate within `timeout`.
+    ///
+    /// Timeouts longer than `u32::MAX` milliseconds (about 49 days) are
+    /// treated as `u32::MAX` milliseconds.
+    pub fn try_for(&self, timeout: Duration) -> Tactic<'ctx> {
+        let ms = timeout.as_millis().try_into().unwrap_or(u32::MAX);
+        unsafe {
+            Tactic::from_raw(
+                self.ctx,
+                Z3_tactic_try_for(self.ctx.z3_ctx, self.z3_tactic, ms),
+            )
+        }
+    }
+
+    /// Create a tactic that applies `t` if `p` evaluates to true on the
+    /// goal, and `otherwise` if it does not.
+    pub fn cond(p: &Probe<'ctx>, t: &Tactic<'ctx>, otherwise: &Tactic<'ctx>) -> Tactic<'ctx> {
+        unsafe {
+            Tactic::from_raw(
+                p.ctx,
+                Z3_tactic_cond(p.ctx.z3_ctx, p.z3_probe, t.z3_tactic, otherwise.z3_tactic),
+            )
+        }
+    }
+
+    /// Create a tactic that applies `t` if `p` evaluates to true on the
+    /// goal, and returns the goal unchanged otherwise.
+    pub fn when(p: &Probe<'ctx>, t: &Tactic<'ctx>) -> Tactic<'ctx> {
+        unsafe { Tactic::from_raw(p.ctx, Z3_tactic_when(p.ctx.z3_ctx, p.z3_probe, t.z3_tactic)) }
+    }
+
+    /// Create a tactic that applies `self` using the given parameters.
+    pub fn with_params(&self, params: &Params<'ctx>) -> Tactic<'ctx> {
+        unsafe {
+            Tactic::from_raw(
+                self.ctx,
+                Z3_tactic_using_params(self.ctx.z3_ctx, self.z3_tactic, params.z3_params),
+            )
+        }
+    }
+
+    /// Apply the tactic to `goal`.
+    ///
+    /// Panics if the tactic fails.
+    ///
+    /// # See also:
+    ///
+    /// - [`Tactic::try_apply()`](#method.try_apply)
+    pub fn apply(&self, goal: &Goal<'ctx>) -> ApplyResult<'ctx> {
+        self.try_apply(goal).unwrap()
+    }
+
+    /// Apply the tactic to `goal`, reporting an error if the tactic fails.
+    pub fn try_apply(&self, goal: &Goal<'ctx>) -> Result<ApplyResult<'ctx>, Z3Error> {
+        let r = unsafe { Z3_tactic_apply(self.ctx.z3_ctx, self.z3_tactic, goal.z3_goal) };
+        self.ctx.check_error()?;
+        Ok(unsafe { ApplyResult::from_raw(self.ctx, r) })
+    }
+
+    pub fn get_help(&self) -> String {
+        unsafe {
+            let s = Z3_tactic_get_help(self.ctx.z3_ctx, self.z3_tactic);
+            CStr::from_ptr(s).to_str().unwrap().to_string()
+        }
+    }
+
+    pub fn get_param_descrs(&self) -> ParamDescrs<'ctx> {
+        unsafe {
+            ParamDescrs::new(
+                self.ctx,
+                Z3_tactic_get_param_descrs(self.ctx.z3_ctx, self.z3_tactic),
+            )
+        }
+    }
+}
+
+impl<'ctx> Clone for Tactic<'ctx> {
+    fn clone(&self) -> Tactic<'ctx> {
+        unsafe { Tactic::from_raw(self.ctx, self.z3_tactic) }
+    }
+}
+
+impl<'ctx> Drop for Tactic<'ctx> {
+    fn drop(&mut self) {
+        unsafe { Z3_tactic_dec_ref(self.ctx.z3_ctx, self.z3_tactic) };
+    }
+}
@@ -3,7 +3,14 @@ extern crate env_logger;
 extern crate log;
 
 extern crate z3;
+extern crate z3_sys;
+
+#[cfg(feature = "arbitrary-size-numeral")]
+extern crate num_bigint;
+#[cfg(feature = "arbitrary-size-numeral")]
+extern crate num_rational;
 use z3::*;
+use z3_sys::*;
 
 #[test]
 fn test_config() {
@@ -39,7 +46,7 @@ fn test_solving() {
 
     let solver = Solver::new(&ctx);
     solver.assert(&x.gt(&y));
-    assert!(solver.check().unwrap());
+    assert_eq!(solver.check(), SatResult::Sat);
 }
 
 #[test]
@@ -58,7 +65,7 @@ fn test_solving_for_model() {
     solver.assert(&y.gt(&zero));
     solver.assert(&y.rem(&seven)._eq(&two));
     solver.assert(&x.add(&[&two]).gt(&seven));
-    assert!(solver.check().unwrap());
+    assert_eq!(solver.check(), SatResult::Sat);
 
     let model = solver.get_model();
     let xv = model.eval(&x).unwrap().as_i64().unwrap();
@@ -81,7 +88,7 @@ fn test_cloning_ast() {
 
     let solver = Solver::new(&ctx);
     solver.assert(&x._eq(&zero));
-    assert!(solver.check().unwrap());
+    assert_eq!(solver.check(), SatResult::Sat);
 
     let model = solver.get_model();
     let xv = model.eval(&x).unwrap().as_i64().unwrap();
@@ -112,10 +119,10 @@ fn test_ast_translate() {
 
     let slv = Solver::new(&destination);
     slv.assert(&translated_a._eq(&destination.from_u64(2)));
-    assert!(slv.check().unwrap());
+    assert_eq!(slv.check(), SatResult::Sat);
 
     slv.assert(&translated_a._eq(&destination.from_u64(3)));
-    assert!(!slv.check().unwrap());
+    assert_eq!(slv.check(), SatResult::Unsat);
 }
 
 #[test]
@@ -129,13 +136,13 @@ fn test_solver_translate() {
 
     let slv = Solver::new(&destination);
     slv.assert(&translated_a._eq(&destination.from_u64(2)));
-    assert!(slv.check().unwrap());
+    assert_eq!(slv.check(), SatResult::Sat);
 
     let translated_slv = slv.translate(&source);
     // Add a new constraint, make the old one unsatisfiable, while the copy remains satisfiable.
     slv.assert(&translated_a._eq(&destination.from_u64(3)));
-    assert!(!slv.check().unwrap());
-    assert!(translated_slv.check().unwrap());
+    assert_eq!(slv.check(), SatResult::Unsat);
+    assert_eq!(translated_slv.check(), SatResult::Sat);
 }
 
 #[test]
@@ -149,7 +156,7 @@ fn test_pb_ops_model() {
     let other_args = vec![&y];
     let solver = Solver::new(&ctx);
     solver.assert(&x.pb_eq(&other_args[..], coeffs, 1));
-    assert!(solver.check().unwrap());
+    assert_eq!(solver.check(), SatResult::Sat);
     let model = solver.get_model();
     let xv = model.eval(&x).unwrap().as_bool().unwrap();
     let yv = model.eval(&y).unwrap().as_bool().unwrap();
@@ -169,5 +176,1670 @@ fn function_ref_count() {
     let _f = ctx.func_decl(ctx.str_sym("f"), &[&int_sort], &int_sort);
     let _g = ctx.func_decl(ctx.str_sym("g"), &[&int_sort], &int_sort);
 
-    assert!(solver.check().unwrap());
+    assert_eq!(solver.check(), SatResult::Sat);
+}
+
+#[test]
+fn test_unsat_core_of_tracked_assertions() {
+    let cfg = Config::new();
+    let ctx = Context::new(&cfg);
+    let solver = Solver::new(&ctx);
+
+    let x = ctx.named_int_const("x");
+    let p1 = ctx.named_bool_const("p1");
+    let p2 = ctx.named_bool_const("p2");
+    let p3 = ctx.named_bool_const("p3");
+
+    solver.assert_and_track(&x.gt(&ctx.from_i64(5)), &p1);
+    solver.assert_and_track(&x.lt(&ctx.from_i64(3)), &p2);
+    solver.assert_and_track(&x.ge(&ctx.from_i64(0)), &p3);
+    assert_eq!(solver.check(), SatResult::Unsat);
+
+    let core = solver.get_unsat_core();
+    assert!(core.contains(&p1));
+    assert!(core.contains(&p2));
+}
+
+#[test]
+fn test_unsat_core_of_assumptions() {
+    let cfg = Config::new();
+    let ctx = Context::new(&cfg);
+    let solver = Solver::new(&ctx);
+
+    let a = ctx.named_bool_const("a");
+    let b = ctx.named_bool_const("b");
+    let c = ctx.named_bool_const("c");
+
+    solver.assert(&a.implies(&b.not()));
+    assert_eq!(
+        solver.check_assumptions(&[a.clone(), b.clone(), c.clone()]),
+        SatResult::Unsat
+    );
+
+    let core = solver.get_unsat_core();
+    assert_eq!(core.len(), 2);
+    assert!(core.contains(&a));
+    assert!(core.contains(&b));
+    assert!(!core.contains(&c));
+
+    assert_eq!(solver.check_assumptions(&[a.clone(), c]), SatResult::Sat);
+    assert!(solver.get_unsat_core().is_empty());
+}
+
+#[test]
+fn test_check_unknown_has_reason() {
+    let mut cfg = Config::new();
+    cfg.set_param_value("rlimit", "1000");
+    let ctx = Context::new(&cfg);
+    let solver = Solver::new(&ctx);
+
+    // x^3 + y^3 = z^3 has no positive integer solutions, but proving that is
+    // well beyond what the solver can do within the resource limit.
+    let x = ctx.named_int_const("x");
+    let y = ctx.named_int_const("y");
+    let z = ctx.named_int_const("z");
+    let zero = ctx.from_i64(0);
+    solver.assert(&x.gt(&zero));
+    solver.assert(&y.gt(&zero));
+    solver.assert(&z.gt(&zero));
+    let x3 = x.mul(&[&x, &x]);
+    let y3 = y.mul(&[&y, &y]);
+    let z3 = z.mul(&[&z, &z]);
+    solver.assert(&x3.add(&[&y3])._eq(&z3));
+
+    assert_eq!(solver.check(), SatResult::Unknown);
+    assert!(solver.get_reason_unknown().is_some());
+}
+
+#[test]
+fn test_optimize_check() {
+    let cfg = Config::new();
+    let ctx = Context::new(&cfg);
+    let opt = Optimize::new(&ctx);
+
+    let x = ctx.named_int_const("x");
+    opt.assert(&x.lt(&ctx.from_i64(10)));
+    opt.maximize(&x);
+    assert_eq!(opt.check(), SatResult::Sat);
+    assert_eq!(opt.get_model().eval(&x).unwrap().as_i64(), Some(9));
+
+    opt.assert(&x.gt(&ctx.from_i64(10)));
+    assert_eq!(opt.check(), SatResult::Unsat);
+}
+
+#[test]
+fn test_sort_mismatch_is_reported() {
+    let cfg = Config::new();
+    let ctx = Context::new(&cfg);
+    let x = ctx.named_int_const("x");
+    let b = ctx.named_bool_const("b");
+
+    let err = x.try_add(&[&b]).err().unwrap();
+    assert!(!err.message().is_empty());
+
+    // The context remains usable after an error.
+    let sum = x.try_add(&[&ctx.from_i64(1)]).unwrap();
+    assert_eq!(format!("{}", sum), "(+ x 1)");
+}
+
+#[test]
+fn test_solver_errors_are_reported() {
+    let cfg = Config::new();
+    let ctx = Context::new(&cfg);
+    let solver = Solver::new(&ctx);
+    let x = ctx.named_int_const("x");
+
+    assert!(solver.try_assert(&x).is_err());
+    assert!(solver.try_pop(1).is_err());
+
+    solver.assert(&x.gt(&x));
+    assert_eq!(solver.check(), SatResult::Unsat);
+    assert!(solver.try_get_model().is_err());
+}
+
+#[test]
+fn test_invalid_params_are_reported() {
+    let cfg = Config::new();
+    let ctx = Context::new(&cfg);
+    let solver = Solver::new(&ctx);
+
+    let params = ctx.params();
+    params.set_bool(&ctx.str_sym("no_such_option"), true);
+    assert!(params.try_validate(&solver.get_param_descrs()).is_err());
+}
+
+#[test]
+fn test_typed_ast() {
+    use z3::ast::{Bool, Int, Real, TypedAst, BV};
+
+    let cfg = Config::new();
+    let ctx = Context::new(&cfg);
+    let solver = Solver::new(&ctx);
+
+    let x = Int::new_const(&ctx, "x");
+    let r = Real::new_const(&ctx, "r");
+    let b = BV::new_const(&ctx, "b", 8);
+    let p = Bool::new_const(&ctx, "p");
+
+    solver.assert(x.gt(&Int::from_i64(&ctx, 3)).as_ast());
+    solver.assert(r._eq(&x.int2real()).as_ast());
+    solver.assert(b._eq(&x.int2bv(8)).as_ast());
+    solver.assert(
+        p.ite(&x, &Int::from_i64(&ctx, 0))
+            ._eq(&Int::from_i64(&ctx, 5))
+            .as_ast(),
+    );
+    assert_eq!(solver.check(), SatResult::Sat);
+
+    let model = solver.get_model();
+    assert_eq!(model.eval(x.as_ast()).unwrap().as_i64(), Some(5));
+    assert_eq!(model.eval(r.as_ast()).unwrap().as_real(), Some((5, 1)));
+    assert_eq!(model.eval(b.as_ast()).unwrap().as_u64(), Some(5));
+    assert_eq!(model.eval(p.as_ast()).unwrap().as_bool(), Some(true));
+}
+
+#[test]
+fn test_typed_ast_conversions() {
+    use z3::ast::{Array, Bool, Dynamic, Int, Real, Set, TypedAst, BV};
+
+    let cfg = Config::new();
+    let ctx = Context::new(&cfg);
+
+    let x = ctx.named_int_const("x");
+    assert!(Int::from_ast(&x).is_some());
+    assert!(Real::from_ast(&x).is_none());
+    assert!(Bool::from_ast(&x).is_none());
+    assert!(BV::from_ast(&x).is_none());
+
+    let arr = Array::new_const(&ctx, "a", &ctx.int_sort(), &ctx.bool_sort());
+    let set = Set::new_const(&ctx, "s", &ctx.int_sort());
+    assert!(Array::from_ast(set.as_ast()).is_some());
+    assert!(Set::from_ast(arr.as_ast()).is_some());
+    let int_arr = Array::new_const(&ctx, "b", &ctx.int_sort(), &ctx.int_sort());
+    assert!(Set::from_ast(int_arr.as_ast()).is_none());
+
+    let elem = int_arr.select(&Int::from_i64(&ctx, 0));
+    assert!(elem.as_int().is_some());
+    assert!(elem.as_bool().is_none());
+
+    let member = set.set_member(&Int::from_i64(&ctx, 1));
+    let dynamic: Dynamic = member.clone().into();
+    assert_eq!(dynamic.as_bool(), Some(member));
+    let ast: Ast = dynamic.into();
+    assert!(Bool::from_ast(&ast).is_some());
+}
+
+#[test]
+fn test_arithmetic_operators() {
+    let cfg = Config::new();
+    let ctx = Context::new(&cfg);
+    let solver = Solver::new(&ctx);
+
+    let x = ctx.named_int_const("x");
+    let y = ctx.named_int_const("y");
+    solver.assert(&(&x * 2 + 1)._eq(&(&y - 3)));
+    solver.assert(&(-&y)._eq(&(&x - 13)));
+    solver.assert(&(&y % 3)._eq(&(&y / 10)));
+    assert_eq!(solver.check(), SatResult::Sat);
+
+    let model = solver.get_model();
+    let xv = model.eval(&x).unwrap().as_i64().unwrap();
+    let yv = model.eval(&y).unwrap().as_i64().unwrap();
+    assert_eq!((xv, yv), (3, 10));
+}
+
+#[test]
+fn test_bitvector_operators() {
+    let cfg = Config::new();
+    let ctx = Context::new(&cfg);
+    let solver = Solver::new(&ctx);
+
+    let a = ctx.named_bitvector_const("a", 8);
+    let b = ctx.named_bitvector_const("b", 8);
+    solver.assert(&(&a ^ &b)._eq(&(ctx.bitvector_sort(8).from_i64(0x0f))));
+    solver.assert(&(&a & 0xf0)._eq(&(ctx.bitvector_sort(8).from_i64(0xf0))));
+    solver.assert(&(&b << 1 | 1)._eq(&(!(&a >> 7) * 2 + &b)));
+    assert_eq!(solver.check(), SatResult::Sat);
+
+    let model = solver.get_model();
+    let av = model.eval(&a).unwrap().as_u64().unwrap();
+    let bv = model.eval(&b).unwrap().as_u64().unwrap();
+    // `>>` is an arithmetic shift, so `a >> 7` is all ones.
+    assert_eq!((av, bv), (0xf0, 0xff));
+}
+
+#[test]
+fn test_boolean_operators() {
+    let cfg = Config::new();
+    let ctx = Context::new(&cfg);
+    let solver = Solver::new(&ctx);
+
+    let p = ctx.named_bool_const("p");
+    let q = ctx.named_bool_const("q");
+    solver.assert(&(&p ^ &q));
+    solver.assert(&(!&p | &q));
+    solver.assert(&!(&p & &q));
+    assert_eq!(solver.check(), SatResult::Sat);
+
+    let model = solver.get_model();
+    assert_eq!(model.eval(&p).unwrap().as_bool(), Some(false));
+    assert_eq!(model.eval(&q).unwrap().as_bool(), Some(true));
+}
+
+#[test]
+fn test_ast_introspection() {
+    let cfg = Config::new();
+    let ctx = Context::new(&cfg);
+
+    let x = ctx.named_int_const("x");
+    let f = FuncDecl::new(
+        &ctx,
+        Symbol::from_string(&ctx, "f"),
+        &[&ctx.int_sort(), &ctx.bool_sort()],
+        &ctx.int_sort(),
+    );
+    let t = f.apply(&[&x.add(&[&ctx.from_i64(1)]), &ctx.from_bool(true)]);
+
+    assert_eq!(t.kind(), Z3_APP_AST);
+    assert_eq!(t.decl().kind(), Z3_OP_UNINTERPRETED);
+    assert_eq!(t.decl().name(), "f");
+    assert_eq!(t.decl().arity(), 2);
+    assert_eq!(t.decl().domain(), vec![ctx.int_sort(), ctx.bool_sort()]);
+    assert_eq!(t.decl().range(), ctx.int_sort());
+
+    let children = t.children();
+    assert_eq!(children.len(), 2);
+    assert_eq!(children[0].decl().kind(), Z3_OP_ADD);
+    assert_eq!(children[0].children(), vec![x.clone(), ctx.from_i64(1)]);
+    assert_eq!(children[1].decl().kind(), Z3_OP_TRUE);
+
+    let one = &children[0].children()[1];
+    assert!(one.is_numeral());
+    assert_eq!(one.kind(), Z3_NUMERAL_AST);
+    assert_eq!(one.num_children(), 0);
+    assert!(x.as_numeral_string().is_none());
+
+    let big = ctx.int_sort().from_i64(i64::MAX) * 4;
+    assert_eq!(
+        big.simplify().as_numeral_string().unwrap(),
+        "36893488147419103228"
+    );
+}
+
+#[test]
+fn test_quantifier_introspection() {
+    let cfg = Config::new();
+    let ctx = Context::new(&cfg);
+
+    let x = ctx.named_int_const("x");
+    let y = ctx.named_bool_const("y");
+    let q = Ast::forall_const(&ctx, &[&x, &y], &x.ge(&ctx.from_i64(0)).or(&[&y]));
+    assert_eq!(q.kind(), Z3_QUANTIFIER_AST);
+    assert!(q.is_forall());
+    assert!(!q.is_exists());
+    assert!(!q.is_app());
+    assert_eq!(q.num_children(), 0);
+
+    let bound = q.quantifier_bound_vars();
+    assert_eq!(bound.len(), 2);
+    assert_eq!(bound[0], ("x".to_owned(), ctx.int_sort()));
+    assert_eq!(bound[1], ("y".to_owned(), ctx.bool_sort()));
+
+    // The innermost bound variable has index 0.
+    let body = q.quantifier_body();
+    assert_eq!(body.decl().kind(), Z3_OP_OR);
+    let ge = body.nth_child(0);
+    assert_eq!(ge.nth_child(0).var_index(), Some(1));
+    assert_eq!(body.nth_child(1).var_index(), Some(0));
+    assert_eq!(x.var_index(), None);
+
+    let e = Ast::exists_const(&ctx, &[&x], &x.ge(&ctx.from_i64(0)));
+    assert!(e.is_exists());
+    assert!(!x.is_forall());
+}
+
+#[test]
+fn test_string_numerals() {
+    let cfg = Config::new();
+    let ctx = Context::new(&cfg);
+    let solver = Solver::new(&ctx);
+
+    let big = "340282366920938463463374607431768211455";
+    let x = ctx.named_bitvector_const("x", 128);
+    let max = Ast::from_str_numeral(&ctx.bitvector_sort(128), big);
+    solver.assert(
+        &x.bvadd(&ctx.bitvector_sort(128).from_i64(1))
+            ._eq(&ctx.bitvector_sort(128).from_i64(0)),
+    );
+    assert_eq!(solver.check(), SatResult::Sat);
+
+    let model = solver.get_model();
+    let value = model.eval(&x).unwrap();
+    assert_eq!(value, max);
+    assert_eq!(value.as_numeral_string().unwrap(), big);
+    assert_eq!(value.as_u64(), None);
+
+    let third = Ast::from_str_numeral(&ctx.real_sort(), "-1/3");
+    assert_eq!(third.as_numeral_string().unwrap(), "-1/3");
+    assert_eq!(third.as_decimal_string(4).unwrap(), "-0.3333?");
+    let decimal = Ast::from_str_numeral(&ctx.real_sort(), "2.5");
+    assert_eq!(decimal.as_real(), Some((5, 2)));
+
+    assert!(Ast::try_from_str_numeral(&ctx.int_sort(), "twelve").is_err());
+    assert_eq!(
+        Ast::try_from_str_numeral(&ctx.int_sort(), "12\0")
+            .unwrap_err()
+            .code(),
+        Z3_INVALID_ARG
+    );
+}
+
+#[test]
+fn test_algebraic_decimal_string() {
+    let cfg = Config::new();
+    let ctx = Context::new(&cfg);
+    let solver = Solver::new(&ctx);
+
+    let x = ctx.named_real_const("x");
+    solver.assert(&x.mul(&[&x])._eq(&ctx.from_real(2, 1)));
+    solver.assert(&x.gt(&ctx.from_real(0, 1)));
+    assert_eq!(solver.check(), SatResult::Sat);
+
+    let value = solver.get_model().eval(&x).unwrap();
+    assert_eq!(value.as_decimal_string(5).unwrap(), "1.41421?");
+}
+
+#[cfg(feature = "arbitrary-size-numeral")]
+#[test]
+fn test_big_numerals() {
+    use num_bigint::{BigInt, BigUint};
+    use num_rational::BigRational;
+    use std::convert::TryFrom;
+    use z3::ast::{Int, Real, TypedAst, BV};
+
+    let cfg = Config::new();
+    let ctx = Context::new(&cfg);
+    let solver = Solver::new(&ctx);
+
+    let two_pow_100 = BigInt::from(1) << 100;
+    let x = Int::new_const(&ctx, "x");
+    solver.assert(x._eq(&Int::from_big_int(&ctx, &two_pow_100)).as_ast());
+    let r = Real::new_const(&ctx, "r");
+    let ratio = BigRational::new(two_pow_100.clone(), BigInt::from(-3));
+    solver.assert(r._eq(&Real::from_big_rational(&ctx, &ratio)).as_ast());
+    let b = BV::new_const(&ctx, "b", 256);
+    solver.assert(
+        b._eq(&BV::from_big_int(&ctx, &BigInt::from(-1), 256))
+            .as_ast(),
+    );
+    assert_eq!(solver.check(), SatResult::Sat);
+
+    let model = solver.get_model();
+    let xv = model.eval(x.as_ast()).unwrap();
+    assert_eq!(xv.as_big_int(), Some(two_pow_100.clone()));
+    assert_eq!(BigUint::try_from(&xv), Ok(BigUint::from(1u32) << 100));
+    assert_eq!(
+        model.eval(r.as_ast()).unwrap().as_big_rational(),
+        Some(ratio)
+    );
+    assert_eq!(
+        model.eval(b.as_ast()).unwrap().as_big_uint(),
+        Some((BigUint::from(1u32) << 256) - 1u32)
+    );
+
+    let neg = Ast::from_big_int(&ctx, &-two_pow_100);
+    assert_eq!(BigUint::try_from(&neg).unwrap_err().code(), Z3_INVALID_ARG);
+    assert!(BigInt::try_from(&ctx.named_int_const("y")).is_err());
+}
+
+#[test]
+fn test_goal_and_tactics() {
+    let cfg = Config::new();
+    let ctx = Context::new(&cfg);
+
+    let x = ctx.named_int_const("x");
+    let y = ctx.named_int_const("y");
+    let goal = Goal::new(&ctx, true, false, false);
+    goal.assert(
+        &x.gt(&ctx.from_i64(0))
+            .and(&[&y._eq(&x.add(&[&ctx.from_i64(1)]))]),
+    );
+    goal.assert(&y.lt(&ctx.from_i64(10)));
+    assert_eq!(goal.size(), 3);
+    assert_eq!(goal.depth(), 0);
+    assert_eq!(goal.precision(), Z3_GOAL_PRECISE);
+    assert!(!goal.is_inconsistent());
+
+    let t = Tactic::new(&ctx, "simplify").and_then(&Tactic::new(&ctx, "solve-eqs"));
+    let result = t.apply(&goal);
+    assert_eq!(result.num_subgoals(), 1);
+    let subgoal = &result.subgoals()[0];
+    assert!(subgoal.depth() > 0);
+    // `y` has been eliminated.
+    assert!(subgoal.size() < goal.size());
+    assert!(subgoal
+        .formulas()
+        .iter()
+        .all(|f| !format!("{}", f).contains('y')));
+
+    let unsat = Goal::new(&ctx, false, false, false);
+    unsat.assert(&x.gt(&x));
+    let result = Tactic::new(&ctx, "simplify").apply(&unsat);
+    let subgoal = &result.subgoals()[0];
+    assert!(subgoal.is_inconsistent());
+    assert!(subgoal.is_decided_unsat());
+
+    assert!(Tactic::list_all(&ctx).contains(&"smt".to_owned()));
+    assert!(Tactic::try_new(&ctx, "no-such-tactic").is_err());
+    assert!(Tactic::try_new(&ctx, "smt\0").is_err());
+    assert!(Tactic::fail(&ctx).try_apply(&goal).is_err());
+}
+
+#[test]
+fn test_tactic_combinators_and_probes() {
+    use std::time::Duration;
+
+    let cfg = Config::new();
+    let ctx = Context::new(&cfg);
+
+    let a = ctx.named_bitvector_const("a", 8);
+    let goal = Goal::new(&ctx, true, false, false);
+    goal.assert(
+        &a.bvmul(&ctx.bitvector_sort(8).from_i64(3))
+            ._eq(&ctx.bitvector_sort(8).from_i64(9)),
+    );
+
+    let is_qfbv = Probe::new(&ctx, "is-qfbv");
+    assert_eq!(is_qfbv.apply(&goal), 1.0);
+    assert_eq!(Probe::new(&ctx, "num-consts").apply(&goal), 1.0);
+    assert!(Probe::try_new(&ctx, "is-qfbv\0").is_err());
+    let small = Probe::new(&ctx, "num-consts").lt(&Probe::constant(&ctx, 5.0));
+    assert_eq!(small.and(&is_qfbv).apply(&goal), 1.0);
+    assert_eq!(small.not().apply(&goal), 0.0);
+    assert!(Probe::list_all(&ctx).contains(&"is-qfbv".to_owned()));
+
+    let bv = Tactic::new(&ctx, "simplify")
+        .and_then(&Tactic::new(&ctx, "bit-blast"))
+        .and_then(&Tactic::new(&ctx, "sat"));
+    let t = Tactic::cond(&is_qfbv, &bv, &Tactic::new(&ctx, "smt"))
+        .try_for(Duration::from_secs(10))
+        .or_else(&Tactic::fail(&ctx))
+        .repeat(2);
+    let t = Tactic::par_or(&ctx, &[&t, &Tactic::new(&ctx, "smt")]);
+    assert!(t.apply(&goal).subgoals()[0].is_decided_sat());
+    let forever = Tactic::new(&ctx, "skip").try_for(Duration::from_secs(u64::MAX));
+    assert_eq!(forever.apply(&goal).subgoals().len(), 1);
+
+    let solver = Solver::from_tactic(&Tactic::when(&is_qfbv, &bv));
+    solver.assert(
+        &a.bvmul(&ctx.bitvector_sort(8).from_i64(3))
+            ._eq(&ctx.bitvector_sort(8).from_i64(9)),
+    );
+    assert_eq!(solver.check(), SatResult::Sat);
+    let av = solver.get_model().eval(&a).unwrap().as_u64().unwrap();
+    assert_eq!(av * 3 % 256, 9);
+
+    solver.push();
+    solver.assert(&a._eq(&ctx.bitvector_sort(8).from_i64(0)));
+    assert_eq!(solver.check(), SatResult::Unsat);
+    solver.pop(1);
+    assert_eq!(solver.check(), SatResult::Sat);
+}
+
+#[test]
+fn test_parse_smtlib2() {
+    let cfg = Config::new();
+    let ctx = Context::new(&cfg);
+
+    let formulas = ctx
+        .parse_smtlib2_string(
+            "(declare-const a Int) (declare-const b Int) \
+             (assert (> a b)) (assert (= b 3)) (check-sat)",
+            &[],
+            &[],
+        )
+        .unwrap();
+    assert_eq!(formulas.len(), 2);
+    assert!(formulas.get(2).is_none());
+
+    let solver = Solver::new(&ctx);
+    for f in formulas.to_vec() {
+        solver.assert(&f);
+    }
+    assert_eq!(solver.check(), SatResult::Sat);
+
+    // Bind names in the input to existing declarations.
+    let s = Sort::uninterpreted(&ctx, &Symbol::from_string(&ctx, "S"));
+    let f = FuncDecl::new(&ctx, Symbol::from_string(&ctx, "f"), &[&s], &s);
+    let formulas = ctx
+        .parse_smtlib2_string("(declare-const c S) (assert (= (f c) c))", &[&s], &[&f])
+        .unwrap();
+    let parsed = formulas.get(0).unwrap();
+    assert_eq!(parsed.nth_child(0), f.apply(&[&parsed.nth_child(1)]));
+    assert_eq!(parsed.nth_child(1).sort(), s);
+
+    let err = ctx
+        .parse_smtlib2_string("(assert (> undeclared 1))", &[], &[])
+        .err()
+        .unwrap();
+    assert_eq!(err.code(), Z3_PARSER_ERROR);
+    assert!(err.message().contains("undeclared"));
+
+    // A failed parse does not leak into the next one.
+    assert!(ctx.parse_smtlib2_string("(assert true)", &[], &[]).is_ok());
+    assert!(ctx.parse_smtlib2_file("bad\0path", &[], &[]).is_err());
+}
+
+#[test]
+fn test_solver_from_string() {
+    let cfg = Config::new();
+    let ctx = Context::new(&cfg);
+
+    let solver = Solver::from_string(
+        &ctx,
+        "(declare-fun p () Bool) (declare-fun q () Bool) (assert (and p (not q)))",
+    )
+    .unwrap();
+    assert_eq!(solver.check(), SatResult::Sat);
+
+    solver
+        .load_string("(declare-fun q () Bool) (assert q)")
+        .unwrap();
+    assert_eq!(solver.check(), SatResult::Unsat);
+
+    // Declarations do not carry over between scripts.
+    assert!(Solver::new(&ctx).load_string("(assert q)").is_err());
+    assert!(Solver::from_string(&ctx, "(assert (and p").is_err());
+    assert!(Solver::new(&ctx)
+        .load_file("/nonexistent/file.smt2")
+        .is_err());
+    assert!(solver.load_file("bad\0path").is_err());
+
+    // A failed load does not leak into the next one.
+    assert!(solver.load_string("(assert (> y 2))").is_err());
+    solver.load_string("(assert true)").unwrap();
+}
+
+#[test]
+fn test_solver_to_smt2() {
+    let cfg = Config::new();
+    let ctx = Context::new(&cfg);
+
+    let empty = Solver::new(&ctx).to_smt2();
+    assert!(empty.contains("(check-sat)"));
+    assert_eq!(
+        Solver::from_string(&ctx, &empty).unwrap().check(),
+        SatResult::Sat
+    );
+
+    let solver = Solver::new(&ctx);
+    let x = ctx.named_int_const("x");
+    let f = ctx.func_decl(ctx.str_sym("f"), &[&ctx.int_sort()], &ctx.int_sort());
+    solver.assert(&f.apply(&[&x]).gt(&x));
+    solver.assert(&f.apply(&[&f.apply(&[&x])])._eq(&x));
+    solver.assert(&f.apply(&[&f.apply(&[&x])]).gt(&f.apply(&[&x])));
+
+    let smt2 = solver.to_smt2();
+    assert!(smt2.contains("(declare-fun f (Int) Int)"));
+    assert_eq!(smt2.matches("(assert").count(), 3);
+
+    let replayed = Solver::from_string(&ctx, &smt2).unwrap();
+    assert_eq!(solver.check(), SatResult::Unsat);
+    assert_eq!(replayed.check(), SatResult::Unsat);
+}
+
+#[test]
+fn test_optimize_to_smt2() {
+    let cfg = Config::new();
+    let ctx = Context::new(&cfg);
+
+    let opt = Optimize::new(&ctx);
+    let x = ctx.named_int_const("x");
+    let y = ctx.named_int_const("y");
+    opt.assert(&x.add(&[&y]).lt(&ctx.from_i64(10)));
+    opt.maximize(&x);
+    opt.minimize(&y);
+
+    let smt2 = opt.to_smt2();
+    assert!(smt2.contains("(declare-fun x () Int)"));
+    assert!(smt2.contains("(maximize x)"));
+    assert!(smt2.contains("(minimize y)"));
+    assert!(smt2.trim_end().ends_with("(check-sat)"));
+}
+
+#[test]
+fn test_interrupt_from_other_thread() {
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::thread;
+    use std::time::Duration;
+
+    let cfg = Config::new();
+    let ctx = Context::new(&cfg);
+
+    // No positive integer solution exists, and Z3 cannot prove it.
+    let x = ctx.named_int_const("x");
+    let y = ctx.named_int_const("y");
+    let z = ctx.named_int_const("z");
+    let x3 = x.mul(&[&x, &x]);
+    let y3 = y.mul(&[&y, &y]);
+    let z3 = z.mul(&[&z, &z]);
+    let zero = ctx.from_i64(0);
+
+    let solver = Solver::new(&ctx);
+    solver.assert(&x3.add(&[&y3])._eq(&z3));
+    solver.assert(&x.gt(&zero));
+    solver.assert(&y.gt(&zero));
+
+    let opt = Optimize::new(&ctx);
+    opt.assert(&x3.add(&[&y3])._eq(&z3));
+    opt.assert(&x.gt(&zero));
+    opt.assert(&y.gt(&zero));
+    opt.minimize(&x);
+
+    let handle = ctx.handle();
+    let done = AtomicBool::new(false);
+    thread::scope(|s| {
+        s.spawn(|| {
+            // Keep interrupting in case the first interrupt arrives before
+            // the check has started.
+            while !done.load(Ordering::SeqCst) {
+                thread::sleep(Duration::from_millis(50));
+                handle.interrupt();
+            }
+        });
+
+        assert_eq!(solver.check(), SatResult::Unknown);
+        assert_eq!(opt.check(), SatResult::Unknown);
+        done.store(true, Ordering::SeqCst);
+    });
+
+    let reason = solver.get_reason_unknown().unwrap();
+    assert!(
+        reason == "canceled" || reason == "interrupted",
+        "{}",
+        reason
+    );
+    assert!(opt.get_reason_unknown().is_some());
+}
+
+#[test]
+fn test_context_is_send() {
+    use std::thread;
+
+    let cfg = Config::new();
+    let ctx = Context::new(&cfg);
+    let result = thread::spawn(move || {
+        let solver = Solver::new(&ctx);
+        let x = ctx.named_int_const("x");
+        solver.assert(&x.gt(&ctx.from_i64(1)));
+        solver.check()
+    })
+    .join()
+    .unwrap();
+    assert_eq!(result, SatResult::Sat);
+}
+
+#[test]
+fn test_check_portfolio() {
+    let cfg = Config::new();
+    let ctx = Context::new(&cfg);
+
+    let default = |_: &Context, _: &Params| {};
+    let seeded = |ctx: &Context, params: &Params| {
+        params.set_uint(&Symbol::from_string(ctx, "random_seed"), 7);
+    };
+    let short = |ctx: &Context, params: &Params| {
+        params.set_uint(&Symbol::from_string(ctx, "rlimit"), 100_000);
+    };
+
+    let solver = Solver::new(&ctx);
+    let p = ctx.named_bool_const("p");
+    solver.assert(&p);
+    solver.assert(&p.not());
+    assert_eq!(
+        solver.check_portfolio(&cfg, &[&default, &seeded]),
+        SatResult::Unsat
+    );
+
+    // No positive integer solution exists, and Z3 cannot prove it.
+    let x = ctx.named_int_const("x");
+    let y = ctx.named_int_const("y");
+    let z = ctx.named_int_const("z");
+    let x3 = x.mul(&[&x, &x]);
+    let y3 = y.mul(&[&y, &y]);
+    let solver = Solver::new(&ctx);
+    solver.assert(&x3.add(&[&y3])._eq(&z.mul(&[&z, &z])));
+    solver.assert(&x.gt(&ctx.from_i64(0)));
+    solver.assert(&y.gt(&ctx.from_i64(0)));
+    assert_eq!(
+        solver.check_portfolio(&cfg, &[&short, &short]),
+        SatResult::Unknown
+    );
+    assert_eq!(solver.check_portfolio(&cfg, &[]), SatResult::Unknown);
+
+    let bad = |ctx: &Context, params: &Params| {
+        params.set_bool(&Symbol::from_string(ctx, "no_such_param"), true);
+    };
+    assert!(solver.try_check_portfolio(&cfg, &[&short, &bad]).is_err());
+
+    let panicking = |_: &Context, _: &Params| panic!("bad configuration");
+    let panicked = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        solver.check_portfolio(&cfg, &[&default, &panicking])
+    }));
+    assert!(panicked.is_err());
+}
+
+#[test]
+fn test_model_inspection() {
+    let cfg = Config::new();
+    let ctx = Context::new(&cfg);
+    let solver = Solver::new(&ctx);
+
+    let s = Sort::uninterpreted(&ctx, &Symbol::from_string(&ctx, "S"));
+    let a = Ast::new_const(&Symbol::from_string(&ctx, "a"), &s);
+    let b = Ast::new_const(&Symbol::from_string(&ctx, "b"), &s);
+    let int = Sort::int(&ctx);
+    let f = FuncDecl::new(&ctx, Symbol::from_string(&ctx, "f"), &[&int], &int);
+    let one = Ast::from_i64(&ctx, 1);
+    let two = Ast::from_i64(&ctx, 2);
+    let x = Ast::new_const(&Symbol::from_string(&ctx, "x"), &int);
+
+    solver.assert(&a._eq(&b).not());
+    solver.assert(&f.apply(&[&one])._eq(&Ast::from_i64(&ctx, 10)));
+    solver.assert(&f.apply(&[&two])._eq(&Ast::from_i64(&ctx, 20)));
+    solver.assert(&x._eq(&Ast::from_i64(&ctx, 5)));
+    assert_eq!(solver.check(), SatResult::Sat);
+    let model = solver.get_model();
+
+    let consts = model.get_const_decls();
+    let mut names: Vec<_> = consts.iter().map(|d| d.name()).collect();
+    names.sort();
+    assert_eq!(names, vec!["a", "b", "x"]);
+    for d in &consts {
+        assert!(model.has_interp(d));
+    }
+    let x_decl = consts.iter().find(|d| d.name() == "x").unwrap();
+    assert_eq!(model.get_const_interp(x_decl).unwrap().as_i64(), Some(5));
+
+    let funcs = model.get_func_decls();
+    assert_eq!(funcs.len(), 1);
+    assert_eq!(funcs[0].name(), "f");
+    let interp = model.get_func_interp(&funcs[0]).unwrap();
+    assert_eq!(interp.arity(), 1);
+    let entries: Vec<_> = interp
+        .entries()
+        .iter()
+        .map(|e| {
+            let args = e.args();
+            assert_eq!(args.len(), 1);
+            (args[0].as_i64().unwrap(), e.value().as_i64().unwrap())
+        })
+        .collect();
+    // Arguments without an entry take the else-value.
+    let lookup = |arg: i64| {
+        entries
+            .iter()
+            .find(|e| e.0 == arg)
+            .map(|e| e.1)
+            .unwrap_or_else(|| interp.else_value().as_i64().unwrap())
+    };
+    assert_eq!(lookup(1), 10);
+    assert_eq!(lookup(2), 20);
+    assert!(!entries.is_empty());
+    assert!(format!("{}", interp).ends_with(&format!("else -> {}]", interp.else_value())));
+
+    let names: Vec<_> = model.iter().map(|d| d.name()).collect();
+    assert_eq!(names.len(), 4);
+    assert_eq!(names[3], "f");
+
+    let sorts = model.get_sorts();
+    assert_eq!(sorts.len(), 1);
+    assert_eq!(sorts[0].to_string(), "S");
+    let universe = model.get_sort_universe(&sorts[0]).unwrap();
+    assert_eq!(universe.len(), 2);
+    assert!(model.get_sort_universe(&int).is_none());
+}
+
+#[test]
+fn test_model_construction() {
+    let cfg = Config::new();
+    let ctx = Context::new(&cfg);
+
+    let int = Sort::int(&ctx);
+    let x = Ast::new_const(&Symbol::from_string(&ctx, "x"), &int);
+    let f = FuncDecl::new(&ctx, Symbol::from_string(&ctx, "f"), &[&int], &int);
+    let one = Ast::from_i64(&ctx, 1);
+
+    let model = Model::new(&ctx);
+    model.add_const_interp(&x.decl(), &Ast::from_i64(&ctx, 3));
+    let interp = model.add_func_interp(&f, &Ast::from_i64(&ctx, 0));
+    interp.add_entry(&[&one], &Ast::from_i64(&ctx, 10));
+    assert_eq!(interp.num_entries(), 1);
+
+    let fx = f.apply(&[&x]);
+    let f1 = f.apply(&[&one]);
+    assert_eq!(model.eval(&x).unwrap().as_i64(), Some(3));
+    assert_eq!(model.eval(&fx).unwrap().as_i64(), Some(0));
+    assert_eq!(model.eval(&f1).unwrap().as_i64(), Some(10));
+
+    let constraint = fx.lt(&f1).and(&[&x.gt(&one)]);
+    assert_eq!(model.eval(&constraint).unwrap().as_bool(), Some(true));
+
+    // A copy is independent of the original.
+    let copy = model.clone();
+    model
+        .get_func_interp(&f)
+        .unwrap()
+        .set_else(&Ast::from_i64(&ctx, 20));
+    assert_eq!(model.eval(&fx).unwrap().as_i64(), Some(20));
+    assert_eq!(copy.eval(&fx).unwrap().as_i64(), Some(0));
+    assert_eq!(model.eval(&constraint).unwrap().as_bool(), Some(false));
+
+    let ctx2 = Context::new(&cfg);
+    let model2 = copy.translate(&ctx2);
+    let constraint2 = constraint.translate(&ctx2);
+    assert_eq!(model2.eval(&constraint2).unwrap().as_bool(), Some(true));
+    assert_eq!(model2.get_const_decls().len(), 1);
+    assert_eq!(model2.get_func_decls().len(), 1);
+}
+
+#[test]
+fn test_model_typed_extraction() {
+    let cfg = Config::new();
+    let ctx = Context::new(&cfg);
+    let solver = Solver::from_string(
+        &ctx,
+        "(declare-const b Bool)
+         (declare-const i Int)
+         (declare-const r Real)
+         (declare-const v (_ BitVec 100))
+         (declare-const s String)
+         (declare-const free Int)
+         (assert b)
+         (assert (= i (- 42)))
+         (assert (= (* 4.0 r) 1.0))
+         (assert (= v (bvshl #x0000000000000000000000001 (_ bv90 100))))
+         (assert (= s \"hello\"))",
+    )
+    .unwrap();
+    assert_eq!(solver.check(), SatResult::Sat);
+    let model = solver.get_model();
+
+    let decls = model.get_const_decls();
+    let var = |name: &str| decls.iter().find(|d| d.name() == name).unwrap().apply(&[]);
+
+    assert_eq!(model.get_bool(&var("b")), Ok(true));
+    assert_eq!(model.get_i64(&var("i")), Ok(-42));
+    assert_eq!(model.get_f64(&var("r")), Ok(0.25));
+    assert_eq!(model.get_f64(&var("i")), Ok(-42.0));
+    assert_eq!(model.get_bv_u128(&var("v")), Ok(1 << 90));
+    assert_eq!(model.get_string(&var("s")), Ok("hello".to_owned()));
+
+    let err = model.get_i64(&var("b")).unwrap_err();
+    assert_eq!(err.code(), Z3_INVALID_ARG);
+    assert!(err.message().contains("i64"));
+    assert!(err.message().contains("true"));
+    assert!(model.get_bool(&var("i")).is_err());
+    assert!(model.get_bv_u128(&var("i")).is_err());
+    assert!(model.get_string(&var("v")).is_err());
+    assert!(model.get_i64(&var("v")).is_err());
+
+    let free = Ast::new_const(&Symbol::from_string(&ctx, "free"), &Sort::int(&ctx));
+    assert_eq!(model.eval_with(&free, false), Some(free.clone()));
+    assert!(model.get_i64(&free).is_ok());
+}
+
+#[test]
+fn test_iter_models() {
+    let cfg = Config::new();
+    let ctx = Context::new(&cfg);
+    let bool_const =
+        |name: &str| Ast::new_const(&Symbol::from_string(&ctx, name), &Sort::bool(&ctx));
+    let a = bool_const("a");
+    let b = bool_const("b");
+    let c = bool_const("c");
+
+    let solver = Solver::new(&ctx);
+    solver.assert(&a.or(&[&b]));
+    solver.assert(&c.or(&[&a]));
+
+    // `c` is not projected, so it does not lead to more models.
+    let mut assignments: Vec<(bool, bool)> = solver
+        .iter_models(&[&a, &b])
+        .map(|m| (m.get_bool(&a).unwrap(), m.get_bool(&b).unwrap()))
+        .collect();
+    assignments.sort();
+    assert_eq!(
+        assignments,
+        vec![(false, true), (true, false), (true, true)]
+    );
+
+    let mut models = solver.iter_models(&[&a, &b, &c]);
+    assert_eq!(models.by_ref().count(), 5);
+    assert_eq!(models.result(), Some(SatResult::Unsat));
+    drop(models);
+    let mut models = solver.iter_models(&[&a, &b]).with_limit(2);
+    assert_eq!(models.by_ref().count(), 2);
+    assert_eq!(models.result(), None);
+    drop(models);
+    assert_eq!(solver.iter_models(&[]).count(), 1);
+
+    // Stopping early still restores the solver.
+    {
+        let mut models = solver.iter_models(&[&a]);
+        assert!(models.next().is_some());
+    }
+    solver.assert(&a);
+    solver.assert(&b);
+    assert_eq!(solver.check(), SatResult::Sat);
+
+    solver.assert(&a.not());
+    assert_eq!(solver.iter_models(&[&a]).count(), 0);
+
+    // Dropping the iterator after the caller popped its scope, or pushed
+    // more, restores the original number of scopes without panicking.
+    solver.push();
+    let models = solver.iter_models(&[&a]);
+    solver.pop(2);
+    drop(models);
+    assert_eq!(solver.get_num_scopes(), 0);
+    let models = solver.iter_models(&[&a]);
+    solver.push();
+    drop(models);
+    assert_eq!(solver.get_num_scopes(), 0);
+
+    // Giving up is reported apart from running out of models.
+    let solver = Solver::new(&ctx);
+    SolverParams::new(&ctx).rlimit(1).apply(&solver);
+    let x = ctx.named_int_const("x");
+    let y = ctx.named_int_const("y");
+    let z = ctx.named_int_const("z");
+    let sum = x
+        .mul(&[&x, &x])
+        .add(&[&y.mul(&[&y, &y]), &z.mul(&[&z, &z])]);
+    solver.assert(&sum._eq(&ctx.from_i64(33)));
+    let mut models = solver.iter_models(&[&x]);
+    assert_eq!(models.by_ref().count(), 0);
+    assert_eq!(models.result(), Some(SatResult::Unknown));
+}
+
+#[test]
+fn test_optimize_objectives_and_soft_constraints() {
+    let cfg = Config::new();
+    let ctx = Context::new(&cfg);
+    let int = |name: &str| Ast::new_const(&Symbol::from_string(&ctx, name), &Sort::int(&ctx));
+
+    // Schedule three jobs in slots 0..3 without overlap; preferences
+    // conflict, so the optimizer has to pick the cheapest violations.
+    let (a, b, c) = (int("a"), int("b"), int("c"));
+    let zero = Ast::from_i64(&ctx, 0);
+    let three = Ast::from_i64(&ctx, 3);
+    let opt = Optimize::new(&ctx);
+    for job in &[&a, &b, &c] {
+        opt.assert(&job.ge(&zero));
+        opt.assert(&job.lt(&three));
+    }
+    opt.assert(&a.distinct(&[&b, &c]));
+    let cost = opt.assert_soft(&a._eq(&zero), 4, Some("prefs"));
+    assert_eq!(opt.assert_soft(&b._eq(&zero), 2, Some("prefs")), cost);
+    assert_eq!(opt.assert_soft(&c._eq(&zero), "1.5", Some("prefs")), cost);
+    let late = opt.maximize(&c);
+    assert_ne!(late, cost);
+
+    assert_eq!(opt.check(), SatResult::Sat);
+    let model = opt.get_model();
+    assert_eq!(model.get_i64(&a), Ok(0));
+    assert_eq!(model.get_i64(&c), Ok(2));
+    assert_eq!(opt.get_lower(cost).as_real(), Some((7, 2)));
+    assert_eq!(opt.get_upper(cost).as_real(), Some((7, 2)));
+    assert_eq!(opt.get_lower(late).as_i64(), Some(2));
+    assert_eq!(opt.get_objectives().len(), 2);
+    assert!(opt.try_assert_soft(&a, "heavy", None).is_err());
+    assert!(opt.try_assert_soft(&a, "1\0", None).is_err());
+
+    // Objectives are tied to their context, even if the other context
+    // has an objective with the same index.
+    let other = Optimize::new(&ctx);
+    assert!(other.try_get_lower(late).is_err());
+    for job in &[&a, &b] {
+        other.assert(&job.ge(&zero));
+        other.assert(&job.lt(&three));
+    }
+    let other_cost = other.minimize(&a);
+    let other_late = other.maximize(&b);
+    assert_eq!(other.check(), SatResult::Sat);
+    assert_eq!(other.get_upper(other_late).as_i64(), Some(2));
+    assert!(other.try_get_lower(cost).is_err());
+    assert!(other.try_get_upper(late).is_err());
+    assert!(opt.try_get_lower(other_cost).is_err());
+
+    assert!(!opt.get_statistics().to_string().is_empty());
+}
+
+#[test]
+fn test_optimize_cores_and_params() {
+    let cfg = Config::new();
+    let ctx = Context::new(&cfg);
+    let boolean = |name: &str| Ast::new_const(&Symbol::from_string(&ctx, name), &Sort::bool(&ctx));
+    let x = Ast::new_const(&Symbol::from_string(&ctx, "x"), &Sort::int(&ctx));
+    let (p, q, r) = (boolean("p"), boolean("q"), boolean("r"));
+
+    let opt = Optimize::new(&ctx);
+    opt.assert_and_track(&x.gt(&Ast::from_i64(&ctx, 10)), &p);
+    opt.assert_and_track(&x.lt(&Ast::from_i64(&ctx, 20)), &q);
+    opt.minimize(&x);
+    assert_eq!(opt.check(), SatResult::Sat);
+    assert_eq!(opt.get_model().get_i64(&x), Ok(11));
+
+    opt.assert(&r.implies(&x.lt(&Ast::from_i64(&ctx, 5))));
+    assert_eq!(
+        opt.check_assumptions(std::slice::from_ref(&r)),
+        SatResult::Unsat
+    );
+    let core = opt.get_unsat_core();
+    assert!(core.contains(&r));
+    assert!(core.contains(&p));
+    assert!(!core.contains(&q));
+    assert_eq!(opt.check(), SatResult::Sat);
+
+    let params = Params::new(&ctx);
+    params.set_symbol(
+        &Symbol::from_string(&ctx, "priority"),
+        &Symbol::from_string(&ctx, "box"),
+    );
+    opt.set_params(&params);
+    params.set_bool(&Symbol::from_string(&ctx, "no_such_param"), true);
+    assert!(opt.try_set_params(&params).is_err());
+}
+
+#[test]
+fn test_floating_point() {
+    let cfg = Config::new();
+    let ctx = Context::new(&cfg);
+    let f32_sort = Sort::float32(&ctx);
+    let f64_sort = Sort::float64(&ctx);
+    let rne = Ast::round_nearest_ties_to_even(&ctx);
+    let rtz = Ast::round_toward_zero(&ctx);
+    let fp = |v: f64| Ast::from_f64(&f64_sort, v);
+    let value = |a: &Ast| a.simplify().as_f64().unwrap();
+
+    assert_eq!(f32_sort.float_ebits(), Some(8));
+    assert_eq!(f32_sort.float_sbits(), Some(24));
+    assert_eq!(Sort::float(&ctx, 11, 53), f64_sort);
+    assert!(Sort::try_float(&ctx, 1, 53).is_err());
+    assert!(Sort::try_float(&ctx, 11, 2).is_err());
+    assert_eq!(Sort::int(&ctx).float_ebits(), None);
+    assert_eq!(rne.sort(), Sort::rounding_mode(&ctx));
+
+    // Numerals and special values.
+    assert_eq!(Ast::from_f64(&f32_sort, 0.1).as_f64(), Some(0.1f32 as f64));
+    assert!(Ast::fp_nan(&f64_sort).as_f64().unwrap().is_nan());
+    assert_eq!(
+        Ast::fp_inf(&f64_sort, true).as_f64(),
+        Some(f64::NEG_INFINITY)
+    );
+    assert!(Ast::fp_zero(&f64_sort, true)
+        .as_f64()
+        .unwrap()
+        .is_sign_negative());
+    assert_eq!(Ast::from_real(&ctx, 1, 4).as_f64(), Some(0.25));
+    assert_eq!(Ast::from_bool(&ctx, true).as_f64(), None);
+    assert!(Ast::try_from_f64(&Sort::int(&ctx), 1.0).is_err());
+
+    // Arithmetic follows IEEE 754, like Rust's f64.
+    let (a, b) = (0.1, 0.2);
+    assert_eq!(value(&fp(a).fp_add(&rne, &fp(b))), a + b);
+    assert_eq!(value(&fp(a).fp_sub(&rne, &fp(b))), a - b);
+    assert_eq!(value(&fp(a).fp_mul(&rne, &fp(b))), a * b);
+    assert_eq!(value(&fp(a).fp_div(&rne, &fp(b))), a / b);
+    assert_eq!(value(&fp(2.0).fp_sqrt(&rne)), 2f64.sqrt());
+    assert_eq!(
+        value(&fp(a).fp_fma(&rne, &fp(b), &fp(1.0))),
+        a.mul_add(b, 1.0)
+    );
+    assert_eq!(value(&fp(-2.5).fp_round_to_integral(&rtz)), -2.0);
+    assert_eq!(value(&fp(5.0).fp_rem(&fp(3.0))), -1.0);
+    assert_eq!(value(&fp(a).fp_min(&fp(b))), a);
+    assert_eq!(value(&fp(a).fp_max(&fp(b))), b);
+    assert_eq!(value(&fp(-a).fp_abs()), a);
+    assert_eq!(value(&fp(a).fp_neg()), -a);
+    let sum = fp(a).fp_add(&rne, &fp(b));
+    assert_eq!(sum.fp_eq(&fp(0.3)).simplify().as_bool(), Some(false));
+    assert_eq!(sum.fp_gt(&fp(0.3)).simplify().as_bool(), Some(true));
+    assert_eq!(fp(a).fp_le(&fp(a)).simplify().as_bool(), Some(true));
+
+    // NaN is not equal to itself.
+    let nan = Ast::fp_nan(&f64_sort);
+    assert_eq!(nan.fp_eq(&nan).simplify().as_bool(), Some(false));
+    assert_eq!(nan.fp_is_nan().simplify().as_bool(), Some(true));
+
+    // Conversions.
+    let bits = fp(1.5).fp_to_ieee_bv().simplify();
+    assert_eq!(bits.as_u64(), Some(1.5f64.to_bits()));
+    assert_eq!(value(&bits.fp_from_ieee_bv(&f64_sort)), 1.5);
+    assert_eq!(
+        fp(-2.7).fp_to_sbv(&rtz, 8).bv2int(true).simplify().as_i64(),
+        Some(-2)
+    );
+    assert_eq!(fp(2.7).fp_to_ubv(&rne, 8).simplify().as_u64(), Some(3));
+    let minus_five = ctx.bitvector_sort(16).from_i64(-5);
+    assert_eq!(value(&minus_five.sbv_to_fp(&rne, &f32_sort)), -5.0);
+    assert_eq!(value(&minus_five.ubv_to_fp(&rne, &f32_sort)), 65531.0);
+    assert_eq!(
+        value(&Ast::from_real(&ctx, 1, 3).real_to_fp(&rne, &f64_sort)),
+        1.0 / 3.0
+    );
+    assert_eq!(fp(0.5).fp_to_real().simplify().as_real(), Some((1, 2)));
+    assert_eq!(value(&fp(0.1).fp_to_fp(&rne, &f32_sort)), 0.1f32 as f64);
+
+    // Solving, and classification of model values.
+    let solver = Solver::new(&ctx);
+    let x = Ast::new_const(&Symbol::from_string(&ctx, "x"), &f64_sort);
+    let y = Ast::new_const(&Symbol::from_string(&ctx, "y"), &f32_sort);
+    solver.assert(&x.fp_add(&rne, &fp(1.0)).fp_eq(&fp(3.5)));
+    solver.assert(&y.fp_is_subnormal());
+    solver.assert(&y.fp_is_positive());
+    assert_eq!(solver.check(), SatResult::Sat);
+    let model = solver.get_model();
+    assert_eq!(model.get_f64(&x), Ok(2.5));
+    let y = model.get_f64(&y).unwrap();
+    assert!(y > 0.0 && y < f32::MIN_POSITIVE as f64);
+    assert_eq!(
+        model.eval(&x.fp_is_infinite()).unwrap().as_bool(),
+        Some(false)
+    );
+}
+
+#[test]
+fn test_strings_and_sequences() {
+    let cfg = Config::new();
+    let ctx = Context::new(&cfg);
+    let string = Sort::string(&ctx);
+    let int = Sort::int(&ctx);
+    let lit = |s: &str| Ast::from_string(&ctx, s);
+    let num = |i: i64| Ast::from_i64(&ctx, i);
+
+    assert_eq!(string.kind(), Z3_SEQ_SORT);
+    assert_eq!(ctx.string_sort(), string);
+    let int_seq = Sort::seq(&ctx, &int);
+    assert_eq!(int_seq.seq_element().unwrap(), int);
+    assert!(int.seq_element().is_none());
+    assert_eq!(Sort::re(&ctx, &string).re_seq().unwrap(), string);
+
+    let hello = lit("hello");
+    assert_eq!(hello.as_string(), Some("hello".to_owned()));
+    assert_eq!(num(1).as_string(), None);
+    assert_eq!(hello.seq_length().simplify().as_i64(), Some(5));
+    assert_eq!(
+        hello
+            .seq_concat(&[&lit(" "), &lit("world")])
+            .simplify()
+            .as_string(),
+        Some("hello world".to_owned())
+    );
+    assert_eq!(
+        hello.seq_contains(&lit("ell")).simplify().as_bool(),
+        Some(true)
+    );
+    assert_eq!(
+        lit("he").seq_prefix_of(&hello).simplify().as_bool(),
+        Some(true)
+    );
+    assert_eq!(
+        lit("he").seq_suffix_of(&hello).simplify().as_bool(),
+        Some(false)
+    );
+    assert_eq!(
+        hello.seq_index_of(&lit("l"), &num(0)).simplify().as_i64(),
+        Some(2)
+    );
+    assert_eq!(
+        hello.seq_substr(&num(1), &num(3)).simplify().as_string(),
+        Some("ell".to_owned())
+    );
+    assert_eq!(
+        hello.seq_at(&num(1)).simplify().as_string(),
+        Some("e".to_owned())
+    );
+    assert_eq!(
+        hello
+            .seq_replace(&lit("l"), &lit("L"))
+            .simplify()
+            .as_string(),
+        Some("heLlo".to_owned())
+    );
+    assert_eq!(lit("42").str_to_int().simplify().as_i64(), Some(42));
+    assert_eq!(
+        num(7).int_to_str().simplify().as_string(),
+        Some("7".to_owned())
+    );
+    assert_eq!(
+        lit("abc").str_lt(&lit("abd")).simplify().as_bool(),
+        Some(true)
+    );
+    assert_eq!(
+        lit("abc").str_le(&lit("ab")).simplify().as_bool(),
+        Some(false)
+    );
+
+    assert_eq!(
+        hello.seq_last_index_of(&lit("l")).simplify().as_i64(),
+        Some(3)
+    );
+
+    // Sequences of integers.
+    let xs = Ast::new_const(&Symbol::from_string(&ctx, "xs"), &int_seq);
+    let solver = Solver::new(&ctx);
+    solver.assert(&xs.seq_length()._eq(&num(2)));
+    solver.assert(&xs.seq_nth(&num(0))._eq(&num(3)));
+    solver.assert(&xs.seq_index_of(&num(4).seq_unit(), &num(0))._eq(&num(1)));
+    solver.assert(&xs.seq_prefix_of(&Ast::seq_empty(&int_seq)).not());
+    assert_eq!(solver.check(), SatResult::Sat);
+    let model = solver.get_model();
+    let expected = num(3).seq_unit().seq_concat(&[&num(4).seq_unit()]);
+    assert_eq!(
+        model.eval(&xs._eq(&expected)).unwrap().as_bool(),
+        Some(true)
+    );
+}
+
+#[test]
+fn test_regular_expressions() {
+    let cfg = Config::new();
+    let ctx = Context::new(&cfg);
+    let string = Sort::string(&ctx);
+    let re_sort = Sort::re(&ctx, &string);
+    let lit = |s: &str| Ast::from_string(&ctx, s);
+    let s = Ast::new_const(&Symbol::from_string(&ctx, "s"), &string);
+
+    let lower = lit("a").re_range(&lit("z"));
+    let word = lower.re_plus();
+    let solver = Solver::new(&ctx);
+
+    // A sanitizer that only lets through lowercase words cannot produce a
+    // tag.
+    solver.push();
+    solver.assert(&s.seq_in_re(&word));
+    solver.assert(&lit("<").seq_prefix_of(&s));
+    assert_eq!(solver.check(), SatResult::Unsat);
+    solver.pop(1);
+
+    // Two or three of `a`-`c`, starting with "ab", but not "abc".
+    solver.push();
+    let abc = lit("a").re_range(&lit("c"));
+    solver.assert(&s.seq_in_re(&abc.re_loop(2, 3)));
+    solver.assert(&s.seq_in_re(&lit("ab").seq_to_re().re_concat(&[&abc.re_option()])));
+    solver.assert(&s.seq_in_re(&lit("abc").seq_to_re().re_complement()));
+    solver.assert(&s.seq_length().gt(&Ast::from_i64(&ctx, 2)));
+    assert_eq!(solver.check(), SatResult::Sat);
+    let value = solver.get_model().get_string(&s).unwrap();
+    assert!(value == "aba" || value == "abb", "{}", value);
+    solver.pop(1);
+
+    let digits = lit("0").re_range(&lit("9")).re_star();
+    let either = word.re_union(&[&digits]);
+    let both = word.re_intersect(&[&digits]);
+    solver.assert(&s.seq_in_re(&either));
+    solver.assert(&s.seq_in_re(&both).not());
+    solver.assert(&s.seq_in_re(&Ast::re_full(&re_sort)));
+    solver.assert(&s.seq_in_re(&Ast::re_empty(&re_sort)).not());
+    solver.assert(&s.seq_length()._eq(&Ast::from_i64(&ctx, 3)));
+    solver.assert(&s.seq_prefix_of(&lit("12345")));
+    assert_eq!(solver.check(), SatResult::Sat);
+    assert_eq!(solver.get_model().get_string(&s), Ok("123".to_owned()));
+}
+
+#[test]
+fn test_recursive_datatypes() {
+    let cfg = Config::new();
+    let ctx = Context::new(&cfg);
+
+    let list = DatatypeBuilder::new(&ctx)
+        .variant("Nil", &[])
+        .variant_with(
+            "Cons",
+            &[
+                ("head", DatatypeAccessor::Sort(&ctx.int_sort())),
+                ("tail", DatatypeAccessor::DatatypeIndex(0)),
+            ],
+        )
+        .finish("List");
+    let (nil, cons) = (&list.variants[0], &list.variants[1]);
+    assert_eq!(cons.accessors[1].range(), list.sort);
+
+    // A list of exactly two elements starting with 3.
+    let xs = Ast::new_const(&Symbol::from_string(&ctx, "xs"), &list.sort);
+    let tail = cons.accessors[1].apply(&[&xs]);
+    let solver = Solver::new(&ctx);
+    solver.assert(&cons.tester.apply(&[&xs]));
+    solver.assert(&cons.tester.apply(&[&tail]));
+    solver.assert(&nil.tester.apply(&[&cons.accessors[1].apply(&[&tail])]));
+    solver.assert(&cons.accessors[0].apply(&[&xs])._eq(&ctx.from_i64(3)));
+    assert_eq!(solver.check(), SatResult::Sat);
+    let model = solver.get_model();
+    let value = model.eval(&xs).unwrap();
+    assert_eq!(
+        cons.accessors[0].apply(&[&value]).simplify().as_i64(),
+        Some(3)
+    );
+    assert_eq!(
+        model.eval(&cons.accessors[1].apply(&[&tail])),
+        Some(nil.constructor.apply(&[]))
+    );
+
+    // Mutually recursive datatypes, referring to each other by name.
+    let tree = DatatypeBuilder::new(&ctx).variant_with(
+        "leaf",
+        &[("value", DatatypeAccessor::Sort(&ctx.int_sort()))],
+    );
+    let tree = tree.variant_with(
+        "node",
+        &[("children", DatatypeAccessor::Datatype("Forest"))],
+    );
+    let forest = DatatypeBuilder::new(&ctx)
+        .variant("empty", &[])
+        .variant_with(
+            "grow",
+            &[
+                ("first", DatatypeAccessor::Datatype("Tree")),
+                ("rest", DatatypeAccessor::Datatype("Forest")),
+            ],
+        );
+    let datatypes = DatatypeBuilder::create_datatypes(vec![("Tree", tree), ("Forest", forest)]);
+    assert_eq!(datatypes.len(), 2);
+    let (tree, forest) = (&datatypes[0], &datatypes[1]);
+    assert_eq!(tree.variants[1].accessors[0].range(), forest.sort);
+    assert_eq!(forest.variants[1].accessors[0].range(), tree.sort);
+
+    // A datatype without a base case has no values, and is rejected.
+    let stream = DatatypeBuilder::new(&ctx)
+        .variant_with("cons", &[("tail", DatatypeAccessor::DatatypeIndex(0))]);
+    assert!(stream.try_finish("Stream").is_err());
+
+    // A node whose only child is a leaf holding 7.
+    let t = Ast::new_const(&Symbol::from_string(&ctx, "t"), &tree.sort);
+    let children = tree.variants[1].accessors[0].apply(&[&t]);
+    let first = forest.variants[1].accessors[0].apply(&[&children]);
+    let solver = Solver::new(&ctx);
+    solver.assert(&tree.variants[1].tester.apply(&[&t]));
+    solver.assert(&forest.variants[1].tester.apply(&[&children]));
+    solver.assert(
+        &forest.variants[0]
+            .tester
+            .apply(&[&forest.variants[1].accessors[1].apply(&[&children])]),
+    );
+    solver.assert(&first._eq(&tree.variants[0].constructor.apply(&[&ctx.from_i64(7)])));
+    assert_eq!(solver.check(), SatResult::Sat);
+    let expected = tree.variants[1]
+        .constructor
+        .apply(&[&forest.variants[1].constructor.apply(&[
+            &tree.variants[0].constructor.apply(&[&ctx.from_i64(7)]),
+            &forest.variants[0].constructor.apply(&[]),
+        ])]);
+    assert_eq!(solver.get_model().eval(&t), Some(expected));
+}
+
+#[test]
+fn test_fixedpoint() {
+    let cfg = Config::new();
+    let ctx = Context::new(&cfg);
+    let node = Sort::bitvector(&ctx, 4);
+    let bool = ctx.bool_sort();
+    let edge = ctx.func_decl(ctx.str_sym("edge"), &[&node, &node], &bool);
+    let path = ctx.func_decl(ctx.str_sym("path"), &[&node, &node], &bool);
+    let x = ctx.named_const("x", &node);
+    let y = ctx.named_const("y", &node);
+    let z = ctx.named_const("z", &node);
+
+    let fp = Fixedpoint::new(&ctx);
+    let params = Params::new(&ctx);
+    params.set_symbol(&ctx.str_sym("engine"), &ctx.str_sym("datalog"));
+    fp.set_params(&params);
+    params.set_bool(&ctx.str_sym("no_such_param"), true);
+    assert!(fp.try_set_params(&params).is_err());
+
+    fp.register_relation(&edge);
+    fp.register_relation(&path);
+    fp.add_fact(&edge, &[1, 2]);
+    fp.add_fact(&edge, &[2, 3]);
+    fp.add_fact(&edge, &[5, 6]);
+    let base = edge.apply(&[&x, &y]).implies(&path.apply(&[&x, &y]));
+    fp.add_rule(&ctx.forall_const(&[&x, &y], &base), Some("base"));
+    let step = edge
+        .apply(&[&x, &y])
+        .and(&[&path.apply(&[&y, &z])])
+        .implies(&path.apply(&[&x, &z]));
+    fp.add_rule(&ctx.forall_const(&[&x, &y, &z], &step), Some("step"));
+    assert!(fp.try_add_fact(&edge, &[1]).is_err());
+    assert!(fp.try_add_rule(&x, None).is_err());
+
+    assert!(fp.get_rules().len() >= 2, "{}", fp);
+
+    let (one, three, six) = (
+        Ast::from_str_numeral(&node, "1"),
+        Ast::from_str_numeral(&node, "3"),
+        Ast::from_str_numeral(&node, "6"),
+    );
+    assert_eq!(fp.query(&path.apply(&[&one, &three])), SatResult::Sat);
+    assert!(fp.get_answer().is_some());
+    assert_eq!(fp.query(&path.apply(&[&one, &six])), SatResult::Unsat);
+    assert_eq!(fp.query_relations(&[&path]), SatResult::Sat);
+    assert!(!fp.get_statistics().to_string().is_empty());
+    assert!(fp.get_param_descrs().to_string().contains("engine"));
+
+    // Horn clauses over integers, loaded from text.
+    let fp = Fixedpoint::new(&ctx);
+    let queries = fp
+        .load_string(
+            "(declare-rel inv (Int))
+             (declare-rel bad ())
+             (declare-var n Int)
+             (rule (inv 0))
+             (rule (=> (and (inv n) (< n 10)) (inv (+ n 3))))
+             (rule (=> (and (inv n) (> n 12)) bad))
+             (query bad)",
+        )
+        .unwrap();
+    assert_eq!(queries.len(), 1);
+    assert_eq!(fp.query(&queries[0]), SatResult::Unsat);
+    assert!(fp.get_answer().is_some());
+    assert!(fp.get_reason_unknown().is_some());
+    let err = fp.load_string("(query undeclared)").unwrap_err();
+    assert_eq!(err.code(), Z3_PARSER_ERROR);
+    assert!(fp.load_file("bad\0path").is_err());
+
+    // A failed load does not leak into the next one.
+    let queries = fp.load_string("(declare-rel q ()) (query q)").unwrap();
+    assert_eq!(fp.query(&queries[0]), SatResult::Unsat);
+    assert!(fp.try_query(&ctx.from_i64(1)).is_err());
+}
+
+#[test]
+fn test_ast_vector_and_map() {
+    let cfg = Config::new();
+    let ctx = Context::new(&cfg);
+    let x = ctx.named_int_const("x");
+    let y = ctx.named_int_const("y");
+
+    let v = AstVector::new(&ctx);
+    assert!(v.is_empty());
+    assert_eq!(v.get(0), None);
+    v.push(&x);
+    v.push(&y);
+    v.resize(4, &ctx.from_i64(0));
+    v.set(3, &x.add(&[&y]));
+    assert_eq!(v.len(), 4);
+    assert_eq!(v.get(2), Some(ctx.from_i64(0)));
+    assert_eq!(v.get(4), None);
+    v.resize(2, &ctx.from_i64(0));
+    assert_eq!(v.to_vec(), [x.clone(), y.clone()]);
+    let names: Vec<String> = (&v).into_iter().map(|a| a.to_string()).collect();
+    assert_eq!(names, ["x", "y"]);
+
+    let other_cfg = Config::new();
+    let other_ctx = Context::new(&other_cfg);
+    let translated = v.translate(&other_ctx);
+    assert_eq!(translated.len(), 2);
+    assert_eq!(translated.get(1), Some(other_ctx.named_int_const("y")));
+
+    let asts: Vec<Ast> = v.into();
+    assert_eq!(asts.len(), 2);
+
+    let m = AstMap::new(&ctx);
+    assert!(m.is_empty());
+    m.insert(&x, &ctx.from_i64(1));
+    m.insert(&y, &ctx.from_i64(2));
+    m.insert(&x, &ctx.from_i64(3));
+    assert_eq!(m.len(), 2);
+    assert!(m.contains_key(&y));
+    assert_eq!(m.get(&x), Some(ctx.from_i64(3)));
+    let mut keys: Vec<String> = m.keys().iter().map(|k| k.to_string()).collect();
+    keys.sort();
+    assert_eq!(keys, ["x", "y"]);
+    m.remove(&y);
+    assert_eq!(m.get(&y), None);
+    assert_eq!(m.len(), 1);
+    m.clear();
+    assert!(m.is_empty());
+}
+
+#[test]
+fn test_solver_state_readback() {
+    let cfg = Config::new();
+    let ctx = Context::new(&cfg);
+    let a = ctx.named_bool_const("a");
+    let b = ctx.named_bool_const("b");
+    let c = ctx.named_bool_const("c");
+    let x = ctx.named_int_const("x");
+
+    let solver = Solver::new(&ctx);
+    assert_eq!(solver.get_num_scopes(), 0);
+    assert!(solver.get_assertions().is_empty());
+    solver.assert(&a);
+    solver.push();
+    solver.assert(&a.implies(&b));
+    solver.push();
+    solver.assert(&b.or(&[&c]));
+    assert_eq!(solver.get_num_scopes(), 2);
+    assert_eq!(
+        solver.get_assertions(),
+        [a.clone(), a.implies(&b), b.or(&[&c])]
+    );
+    assert!(solver.get_units().contains(&a));
+    assert!(!solver.get_non_units().contains(&a));
+    assert!(solver.try_get_trail().is_err());
+    solver.pop(1);
+    assert_eq!(solver.get_num_scopes(), 1);
+    assert_eq!(solver.get_assertions(), [a.clone(), a.implies(&b)]);
+
+    // Which of `b` and `c` are fixed by the assertions and assumptions?
+    let (result, consequences) = solver.get_consequences(&[], &[b.clone(), c.clone()]);
+    assert_eq!(result, SatResult::Sat);
+    let true_ = ctx.from_bool(true);
+    assert_eq!(consequences, [true_.implies(&b)]);
+    let (result, consequences) = solver.get_consequences(&[c.not()], &[b.clone(), c.clone()]);
+    assert_eq!(result, SatResult::Sat);
+    assert_eq!(consequences.len(), 2);
+    assert!(consequences.contains(&c.not().implies(&c.not())));
+    let (result, _) = solver
+        .try_get_consequences(&[b.not()], std::slice::from_ref(&c))
+        .unwrap();
+    assert_eq!(result, SatResult::Unsat);
+
+    solver.assert(&x.gt(&ctx.from_i64(2)));
+    solver.assert(&x.lt(&ctx.from_i64(4)));
+    let (result, consequences) = solver.get_consequences(&[], std::slice::from_ref(&x));
+    assert_eq!(result, SatResult::Sat);
+    assert_eq!(consequences, [true_.implies(&x._eq(&ctx.from_i64(3)))]);
+
+    let solver = Solver::new_for_logic(&ctx, "QF_FD");
+    solver.assert(&a);
+    solver.assert(&a.implies(&b));
+    assert_eq!(solver.check(), SatResult::Sat);
+    let trail = solver.get_trail();
+    assert!(trail.contains(&a) && trail.contains(&b), "{:?}", trail);
+    assert!(Solver::try_new_for_logic(&ctx, "NOT_A_LOGIC").is_err());
+}
+
+#[test]
+fn test_solver_params() {
+    let cfg = Config::new();
+    let ctx = Context::new(&cfg);
+    let solver = Solver::new(&ctx);
+
+    let params = Params::new(&ctx);
+    params.set_str(&ctx.str_sym("logic"), "QF_LIA");
+    params.set_uint(&ctx.str_sym("rlimit"), 1_000_000);
+    solver.set_params(&params);
+    assert!(params.to_string().contains("QF_LIA"));
+
+    params.set_bool(&ctx.str_sym("no_such_param"), true);
+    let err = solver.try_set_params(&params).unwrap_err();
+    assert!(err.to_string().contains("no_such_param"), "{}", err);
+    let params = Params::new(&ctx);
+    params.set_bool(&ctx.str_sym("timeout"), true);
+    assert!(solver.try_set_params(&params).is_err());
+
+    let builder = SolverParams::new(&ctx)
+        .timeout_msec(60_000)
+        .rlimit(10_000_000)
+        .random_seed(7)
+        .unsat_core(true)
+        .mbqi(false)
+        .logic("QF_LIA");
+    builder.apply(&solver);
+    let shown = builder.params().to_string();
+    assert!(shown.contains("random_seed 7"), "{}", shown);
+    assert!(shown.contains("mbqi false"), "{}", shown);
+    assert!(shown.contains("unsat_core true"), "{}", shown);
+
+    builder
+        .params()
+        .set_bool(&ctx.str_sym("no_such_param"), true);
+    assert!(builder.try_apply(&solver).is_err());
+
+    // A tiny resource limit makes hard problems inconclusive.
+    let solver = Solver::new(&ctx);
+    SolverParams::new(&ctx).rlimit(1).apply(&solver);
+    let x = ctx.named_int_const("x");
+    let y = ctx.named_int_const("y");
+    let z = ctx.named_int_const("z");
+    let sum = x
+        .mul(&[&x, &x])
+        .add(&[&y.mul(&[&y, &y]), &z.mul(&[&z, &z])]);
+    solver.assert(&sum._eq(&ctx.from_i64(33)));
+    assert_eq!(solver.check(), SatResult::Unknown);
+}
+
+#[test]
+fn test_statistics() {
+    let cfg = Config::new();
+    let ctx = Context::new(&cfg);
+    let x = ctx.named_int_const("x");
+    let y = ctx.named_int_const("y");
+
+    let solver = Solver::new(&ctx);
+    solver.assert(&x.gt(&y));
+    solver.assert(&y.gt(&x).or(&[&x._eq(&y)]));
+    assert_eq!(solver.check(), SatResult::Unsat);
+    let stats = solver.get_statistics();
+    assert!(!stats.is_empty());
+    assert_eq!(stats.iter().count(), stats.len());
+    for (key, value) in &stats {
+        assert_eq!(stats.get(&key), Some(value));
+    }
+    match stats.get("memory") {
+        Some(StatsValue::Double(mb)) => assert!(mb > 0.0),
+        other => panic!("{:?}", other),
+    }
+    assert_eq!(stats.get("no such statistic"), None);
+
+    let opt = Optimize::new(&ctx);
+    opt.assert(&x.lt(&ctx.from_i64(10)));
+    opt.maximize(&x);
+    assert_eq!(opt.check(), SatResult::Sat);
+    let stats = opt.get_statistics();
+    assert!(stats
+        .iter()
+        .any(|(_, value)| matches!(value, StatsValue::UInt(_))));
 }